@@ -0,0 +1,199 @@
+use std::{
+    cmp::Ordering,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use num_bigint::BigInt;
+use num_traits::{
+    identities::{One, Zero},
+    Num,
+};
+
+use super::Rational;
+
+pub trait Ring:
+    Clone
+    + PartialEq
+    + Zero
+    + One
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+}
+
+pub trait EuclideanRing: Ring {
+    // (q, r) with self = q * other + r and r smaller than other
+    fn div_rem(&self, other: &Self) -> (Self, Self);
+
+    // The associate of self that stands for all of them, |self| for integers
+    fn normalize(&self) -> Self;
+
+    // Normalized, so gcd(-4, 0) = 4
+    fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let (_, r) = a.div_rem(&b);
+            a = b;
+            b = r;
+        }
+        a.normalize()
+    }
+}
+
+pub trait Field: Ring + Div<Output = Self> {
+    fn inv(&self) -> Self {
+        Self::one() / self.clone()
+    }
+}
+
+pub trait OrderedField: Field + PartialOrd {
+    fn abs(&self) -> Self {
+        if *self < Self::zero() {
+            -self.clone()
+        } else {
+            self.clone()
+        }
+    }
+}
+
+// The continuous operations numerical algorithms (QR, eigenvalues, SVD) need
+pub trait RealField: OrderedField + Num + Copy {
+    fn sqrt(self) -> Self;
+    fn copysign(self, sign: Self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn acos(self) -> Self;
+    fn epsilon() -> Self;
+    fn infinity() -> Self;
+    fn from_f64(x: f64) -> Self;
+    // A total order that also places NaN, for sorting without panics
+    fn total_cmp(&self, other: &Self) -> Ordering;
+
+    fn from_usize(n: usize) -> Self {
+        Self::from_f64(n as f64)
+    }
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Ring for $t {}
+
+            impl EuclideanRing for $t {
+                fn div_rem(&self, other: &Self) -> (Self, Self) {
+                    (self.div_euclid(*other), self.rem_euclid(*other))
+                }
+
+                fn normalize(&self) -> Self {
+                    <$t>::abs(*self)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_float {
+    ($($t:ident),*) => {
+        $(
+            impl Ring for $t {}
+            impl Field for $t {}
+            impl OrderedField for $t {}
+
+            impl RealField for $t {
+                fn sqrt(self) -> Self {
+                    $t::sqrt(self)
+                }
+
+                fn copysign(self, sign: Self) -> Self {
+                    $t::copysign(self, sign)
+                }
+
+                fn powf(self, n: Self) -> Self {
+                    $t::powf(self, n)
+                }
+
+                fn acos(self) -> Self {
+                    $t::acos(self)
+                }
+
+                fn epsilon() -> Self {
+                    $t::EPSILON
+                }
+
+                fn infinity() -> Self {
+                    $t::INFINITY
+                }
+
+                fn from_f64(x: f64) -> Self {
+                    x as $t
+                }
+
+                fn total_cmp(&self, other: &Self) -> Ordering {
+                    $t::total_cmp(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_float!(f32, f64);
+
+impl Ring for BigInt {}
+
+// Euclidean division like the primitive integers, so the remainder is never
+// negative and gcd agrees with i64
+impl EuclideanRing for BigInt {
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        (
+            num_traits::Euclid::div_euclid(self, other),
+            num_traits::Euclid::rem_euclid(self, other),
+        )
+    }
+
+    fn normalize(&self) -> Self {
+        num_traits::Signed::abs(self)
+    }
+}
+
+impl Ring for Rational {}
+impl Field for Rational {}
+impl OrderedField for Rational {}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigInt;
+
+    use super::EuclideanRing;
+
+    #[test]
+    fn div_rem_is_euclidean() {
+        for (a, b) in [(7i64, 3i64), (-7, 3), (7, -3), (-7, -3)] {
+            let (q, r) = a.div_rem(&b);
+            assert_eq!(q * b + r, a);
+            assert!((0..b.abs()).contains(&r));
+
+            let (q, r) = BigInt::from(a).div_rem(&BigInt::from(b));
+            assert_eq!(
+                (q, r),
+                (BigInt::from(a.div_euclid(b)), BigInt::from(a.rem_euclid(b)))
+            );
+        }
+    }
+
+    #[test]
+    fn gcd_is_non_negative() {
+        for (a, b, g) in [
+            (-4i64, 0i64, 4i64),
+            (0, -4, 4),
+            (-12, 18, 6),
+            (12, -18, 6),
+            (0, 0, 0),
+        ] {
+            assert_eq!(a.gcd(&b), g);
+            assert_eq!(BigInt::from(a).gcd(&BigInt::from(b)), BigInt::from(g));
+        }
+    }
+}
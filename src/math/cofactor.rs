@@ -0,0 +1,272 @@
+use num_traits::identities;
+
+use super::{Field, LinAlgError, Mat2d, Ring};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpansionLine {
+    Row(usize),
+    Col(usize),
+    // Expand along the row or column with the most zeros
+    Auto,
+}
+
+#[derive(Debug, Clone)]
+pub struct CofactorNode<T> {
+    // Position of the entry this node was expanded from in its parent, None at the root
+    pub position: Option<(usize, usize)>,
+    pub entry: T,
+    pub sign: i8,
+    pub minor: Mat2d<T>,
+    pub value: T,
+    // The resolved line this node was expanded along, None for 1x1 and empty minors
+    pub line: Option<ExpansionLine>,
+    pub children: Vec<CofactorNode<T>>,
+}
+
+impl<T> CofactorNode<T> {
+    // Pre-order walk yielding each node together with its depth
+    pub fn iter(&self) -> CofactorIter<'_, T> {
+        CofactorIter {
+            stack: vec![(0, self)],
+        }
+    }
+
+    // The signed contribution of this node to its parent's determinant
+    pub fn term(&self) -> T
+    where
+        T: Ring,
+    {
+        let term = self.entry.clone() * self.value.clone();
+        if self.sign < 0 {
+            -term
+        } else {
+            term
+        }
+    }
+}
+
+pub struct CofactorIter<'a, T> {
+    stack: Vec<(usize, &'a CofactorNode<T>)>,
+}
+
+impl<'a, T> Iterator for CofactorIter<'a, T> {
+    type Item = (usize, &'a CofactorNode<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, node) = self.stack.pop()?;
+        self.stack
+            .extend(node.children.iter().rev().map(|child| (depth + 1, child)));
+        Some((depth, node))
+    }
+}
+
+impl<T: Clone> Mat2d<T> {
+    pub fn minor(&self, i: usize, j: usize) -> Self {
+        let mut mat = self.clone();
+        mat.remove_row(i);
+        mat.remove_col(j);
+        mat
+    }
+
+    pub fn cofactor(&self, i: usize, j: usize) -> Result<T, LinAlgError>
+    where
+        T: Field,
+    {
+        let det = self.minor(i, j).determinant()?;
+        Ok(if (i + j).is_multiple_of(2) { det } else { -det })
+    }
+
+    pub fn cofactor_matrix(&self) -> Result<Self, LinAlgError>
+    where
+        T: Field,
+    {
+        let (m, n) = self.shape();
+        if m != n {
+            return Err(LinAlgError::NotSquare { shape: (m, n) });
+        }
+
+        let mut mat = Self::zeros(m, n);
+        for i in 0..m {
+            for j in 0..n {
+                mat[i][j] = self.cofactor(i, j)?;
+            }
+        }
+
+        Ok(mat)
+    }
+
+    pub fn adjugate(&self) -> Result<Self, LinAlgError>
+    where
+        T: Field,
+    {
+        Ok(self.cofactor_matrix()?.transpose())
+    }
+
+    pub fn determinant_by_cofactors(
+        &self,
+        line: ExpansionLine,
+    ) -> Result<CofactorNode<T>, LinAlgError>
+    where
+        T: Ring,
+    {
+        let (m, n) = self.shape();
+        if m != n {
+            return Err(LinAlgError::NotSquare { shape: (m, n) });
+        }
+        // the line must exist: expanding along row i needs i + 1 rows
+        match line {
+            ExpansionLine::Row(i) if i >= n => {
+                return Err(LinAlgError::DimensionMismatch {
+                    expected: (i + 1, n),
+                    found: (m, n),
+                })
+            }
+            ExpansionLine::Col(j) if j >= n => {
+                return Err(LinAlgError::DimensionMismatch {
+                    expected: (m, j + 1),
+                    found: (m, n),
+                })
+            }
+            _ => {}
+        }
+
+        Ok(self.expand(None, T::one(), 1, line))
+    }
+
+    fn expand(
+        &self,
+        position: Option<(usize, usize)>,
+        entry: T,
+        sign: i8,
+        line: ExpansionLine,
+    ) -> CofactorNode<T>
+    where
+        T: Ring,
+    {
+        let (n, _) = self.shape();
+        let leaf = |value| CofactorNode {
+            position,
+            entry: entry.clone(),
+            sign,
+            minor: self.clone(),
+            value,
+            line: None,
+            children: Vec::new(),
+        };
+
+        match n {
+            0 => return leaf(T::one()),
+            1 => return leaf(self[0][0].clone()),
+            _ => {}
+        }
+
+        let line = match line {
+            ExpansionLine::Auto => self.sparsest_line(),
+            line => line,
+        };
+        let entries: Vec<(usize, usize)> = match line {
+            ExpansionLine::Row(i) => (0..n).map(|j| (i, j)).collect(),
+            ExpansionLine::Col(j) => (0..n).map(|i| (i, j)).collect(),
+            ExpansionLine::Auto => unreachable!(),
+        };
+
+        let children: Vec<_> = entries
+            .into_iter()
+            .filter(|&(i, j)| !self[i][j].is_zero())
+            .map(|(i, j)| {
+                let sign = if (i + j).is_multiple_of(2) { 1 } else { -1 };
+                self.minor(i, j)
+                    .expand(Some((i, j)), self[i][j].clone(), sign, ExpansionLine::Auto)
+            })
+            .collect();
+        let value = children
+            .iter()
+            .fold(T::zero(), |acc, child| acc + child.term());
+
+        CofactorNode {
+            position,
+            entry,
+            sign,
+            minor: self.clone(),
+            value,
+            line: Some(line),
+            children,
+        }
+    }
+
+    fn sparsest_line(&self) -> ExpansionLine
+    where
+        T: identities::Zero,
+    {
+        let (n, _) = self.shape();
+        let mut best = (ExpansionLine::Row(0), 0);
+
+        for i in 0..n {
+            let zeros = self[i].iter().filter(|x| x.is_zero()).count();
+            if zeros > best.1 {
+                best = (ExpansionLine::Row(i), zeros);
+            }
+        }
+        for j in 0..n {
+            let zeros = self.iter().filter(|v| v[j].is_zero()).count();
+            if zeros > best.1 {
+                best = (ExpansionLine::Col(j), zeros);
+            }
+        }
+
+        best.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::rational::rational;
+
+    #[test]
+    fn expansion_agrees_along_every_line() {
+        let a = Mat2d::from([[2i64, 0, 1], [1, 3, 0], [0, 1, 4]]);
+        for line in [
+            ExpansionLine::Row(0),
+            ExpansionLine::Row(2),
+            ExpansionLine::Col(1),
+            ExpansionLine::Auto,
+        ] {
+            assert_eq!(a.determinant_by_cofactors(line).unwrap().value, 25);
+        }
+    }
+
+    #[test]
+    fn tree_skips_zero_entries() {
+        let a = Mat2d::from([[1i64, 2, 3], [0, 4, 5], [0, 0, 6]]);
+        let tree = a.determinant_by_cofactors(ExpansionLine::Auto).unwrap();
+        // column 0 and row 2 have two zeros each, and rows come first
+        assert_eq!(tree.line, Some(ExpansionLine::Row(2)));
+        assert_eq!(tree.children.len(), 1);
+        assert_eq!(tree.children[0].position, Some((2, 2)));
+        assert_eq!(tree.children[0].term(), 24);
+        assert_eq!(tree.iter().map(|(depth, _)| depth).max(), Some(2));
+    }
+
+    #[test]
+    fn adjugate_inverts_up_to_the_determinant() {
+        let a = Mat2d::from([[2, 1, 0], [1, 3, 1], [0, 1, 4]]).map(|&x| rational(x, 1));
+        let det = a.determinant().unwrap();
+        let product = (&a * &a.adjugate().unwrap()).unwrap();
+        assert_eq!(product, Mat2d::identity(3, 3).unwrap() * det);
+        assert_eq!(a.cofactor(0, 1).unwrap(), rational(-4, 1));
+    }
+
+    #[test]
+    fn rejects_missing_lines() {
+        let a = Mat2d::from([[1i64, 2], [3, 4]]);
+        assert!(matches!(
+            a.determinant_by_cofactors(ExpansionLine::Row(2)),
+            Err(LinAlgError::DimensionMismatch { .. })
+        ));
+        assert!(matches!(
+            Mat2d::<i64>::zeros(1, 2).determinant_by_cofactors(ExpansionLine::Auto),
+            Err(LinAlgError::NotSquare { shape: (1, 2) })
+        ));
+    }
+}
@@ -0,0 +1,164 @@
+use num_complex::Complex;
+use num_traits::{identities::Zero, Num};
+
+use super::{vec, Field, Mat2d, RealField, Ring};
+
+// Gaussian integers for integer T, exact complex rationals for Rational
+impl<T: Ring + Num> Ring for Complex<T> {}
+impl<T: Field + Num> Field for Complex<T> {}
+
+// |z|, without going through Complex::norm which needs a Float
+pub fn modulus<T: RealField>(z: Complex<T>) -> T {
+    z.norm_sqr().sqrt()
+}
+
+fn conj<T: Ring>(z: &Complex<T>) -> Complex<T> {
+    Complex::new(z.re.clone(), -z.im.clone())
+}
+
+impl<T: Clone + Zero> vec::Vec<T> {
+    pub fn to_complex(&self) -> vec::Vec<Complex<T>> {
+        self.iter()
+            .map(|x| Complex::new(x.clone(), T::zero()))
+            .collect()
+    }
+}
+
+impl<T: Ring + Num> vec::Vec<Complex<T>> {
+    pub fn conj(&self) -> Self {
+        self.iter().map(conj).collect()
+    }
+
+    pub fn re(&self) -> vec::Vec<T> {
+        self.iter().map(|z| z.re.clone()).collect()
+    }
+
+    pub fn im(&self) -> vec::Vec<T> {
+        self.iter().map(|z| z.im.clone()).collect()
+    }
+
+    // <u, v> = Σ conj(u_i) v_i, conjugate-linear in the first argument
+    pub fn inner(&self, other: &Self) -> Complex<T> {
+        assert!(
+            self.len() == other.len(),
+            "two vectors in an inner product must have the same length"
+        );
+        self.iter()
+            .zip(other.iter())
+            .fold(Complex::zero(), |acc, (a, b)| acc + conj(a) * b.clone())
+    }
+
+    // <v, v>, which is always real
+    pub fn norm_sqr(&self) -> T {
+        self.iter().fold(T::zero(), |acc, z| acc + z.norm_sqr())
+    }
+
+    pub fn norm(&self) -> T
+    where
+        T: RealField,
+    {
+        self.norm_sqr().sqrt()
+    }
+}
+
+impl<T: Clone + Zero> Mat2d<T> {
+    pub fn to_complex(&self) -> Mat2d<Complex<T>> {
+        self.map(|x| Complex::new(x.clone(), T::zero()))
+    }
+}
+
+impl<T: Ring + Num> Mat2d<Complex<T>> {
+    pub fn conj(&self) -> Self {
+        self.map(conj)
+    }
+
+    pub fn re(&self) -> Mat2d<T> {
+        self.map(|z| z.re.clone())
+    }
+
+    pub fn im(&self) -> Mat2d<T> {
+        self.map(|z| z.im.clone())
+    }
+
+    // Conjugate transpose A*
+    pub fn adjoint(&self) -> Self {
+        self.transpose().conj()
+    }
+
+    // A = A*
+    pub fn is_hermitian(&self) -> bool {
+        let (m, n) = self.shape();
+        m == n && *self == self.adjoint()
+    }
+
+    // A*A = I, with every entry within tol of the identity
+    pub fn is_unitary(&self, tol: T) -> bool
+    where
+        T: RealField,
+    {
+        let (m, n) = self.shape();
+        if m != n {
+            return false;
+        }
+        let Ok(product) = &self.adjoint() * self else {
+            return false;
+        };
+        let identity = Self::identity(n, n).unwrap();
+        (0..n).all(|i| (0..n).all(|j| modulus(product[i][j] - identity[i][j]) <= tol))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::rational::rational;
+
+    fn c(re: f64, im: f64) -> Complex<f64> {
+        Complex::new(re, im)
+    }
+
+    #[test]
+    fn adjoint_and_hermitian() {
+        let a = Mat2d::from([[c(2.0, 0.0), c(1.0, -1.0)], [c(1.0, 1.0), c(3.0, 0.0)]]);
+        assert!(a.is_hermitian());
+        assert_eq!(a.adjoint(), a);
+
+        let b = Mat2d::from([[c(0.0, 1.0), c(2.0, 0.0)], [c(0.0, 0.0), c(1.0, 0.0)]]);
+        assert!(!b.is_hermitian());
+        assert_eq!(
+            b.adjoint(),
+            Mat2d::from([[c(0.0, -1.0), c(0.0, 0.0)], [c(2.0, 0.0), c(1.0, 0.0)]])
+        );
+        assert_eq!(b.re(), Mat2d::from([[0.0, 2.0], [0.0, 1.0]]));
+        assert_eq!(b.im(), Mat2d::from([[1.0, 0.0], [0.0, 0.0]]));
+    }
+
+    #[test]
+    fn unitary_matrices() {
+        let s = std::f64::consts::FRAC_1_SQRT_2;
+        let u = Mat2d::from([[c(s, 0.0), c(0.0, s)], [c(0.0, s), c(s, 0.0)]]);
+        assert!(u.is_unitary(1e-12));
+        assert!(!(&u * c(2.0, 0.0)).is_unitary(1e-12));
+        assert!(!Mat2d::<Complex<f64>>::zeros(2, 3).is_unitary(1e-12));
+    }
+
+    #[test]
+    fn vector_inner_product_is_conjugate_linear() {
+        let u = vec::Vec::from([c(1.0, 1.0), c(0.0, 2.0)]);
+        let v = vec::Vec::from([c(2.0, 0.0), c(1.0, 0.0)]);
+        assert_eq!(u.inner(&v), c(2.0, -4.0));
+        assert_eq!(u.norm_sqr(), 6.0);
+        assert_eq!(u.conj().im(), vec::Vec::from([-1.0, -2.0]));
+    }
+
+    #[test]
+    fn exact_complex_elimination() {
+        let i = Complex::new(rational(0, 1), rational(1, 1));
+        let one = Complex::new(rational(1, 1), rational(0, 1));
+        let a = Mat2d::from([[one.clone(), i.clone()], [i, one.clone()]]);
+        // det = 1 - i² = 2
+        assert_eq!(a.determinant().unwrap(), one.clone() + one);
+        let inverse = a.inverse().unwrap();
+        assert_eq!((&a * &inverse).unwrap(), Mat2d::identity(2, 2).unwrap());
+    }
+}
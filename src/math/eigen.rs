@@ -0,0 +1,452 @@
+use std::ops::Range;
+
+use super::{
+    complex::modulus,
+    mat::kernel_basis,
+    pivot::{best_row, pivot_column},
+    tolerance::ZeroTest,
+    vec, LinAlgError, Mat2d, RealField, Tolerance,
+};
+use num_complex::Complex;
+
+const MAX_ITERATIONS: usize = 30;
+
+#[derive(Debug, Clone)]
+pub struct EigenSpace<T> {
+    pub value: Complex<T>,
+    pub algebraic_multiplicity: usize,
+    // Basis of the null space of (A - λI)
+    pub vectors: Vec<vec::Vec<Complex<T>>>,
+}
+
+impl<T> EigenSpace<T> {
+    pub fn geometric_multiplicity(&self) -> usize {
+        self.vectors.len()
+    }
+
+    pub fn is_defective(&self) -> bool {
+        self.vectors.len() < self.algebraic_multiplicity
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Eigen<T> {
+    // Every eigenvalue repeated according to its algebraic multiplicity
+    pub values: Vec<Complex<T>>,
+    pub spaces: Vec<EigenSpace<T>>,
+}
+
+impl<T: RealField> Eigen<T> {
+    pub fn is_diagonalizable(&self) -> bool {
+        self.spaces.iter().all(|space| !space.is_defective())
+    }
+}
+
+impl<T: RealField> Mat2d<T> {
+    pub fn hessenberg(&self) -> Result<Self, LinAlgError> {
+        let (m, n) = self.shape();
+        if m != n {
+            return Err(LinAlgError::NotSquare { shape: (m, n) });
+        }
+
+        let mut a = self.clone();
+        let two = T::one() + T::one();
+
+        for c in 0..n.saturating_sub(2) {
+            let x: vec::Vec<T> = (c + 1..n).map(|i| a[i][c]).collect();
+            let x_norm = x.iter().fold(T::zero(), |acc, x| acc + *x * *x).sqrt();
+            if x_norm == T::zero() {
+                continue;
+            }
+
+            let alpha = if x[0] < T::zero() { x_norm } else { -x_norm };
+            let mut v = x;
+            v[0] = v[0] - alpha;
+            let v_norm = v.iter().fold(T::zero(), |acc, x| acc + *x * *x).sqrt();
+            if v_norm == T::zero() {
+                continue;
+            }
+            v /= v_norm;
+
+            // A <- H A H with H = I - 2vv^T acting on rows/columns c + 1..n
+            for j in 0..n {
+                let s = (c + 1..n).fold(T::zero(), |acc, i| acc + v[i - c - 1] * a[i][j]) * two;
+                for i in c + 1..n {
+                    a[i][j] = a[i][j] - s * v[i - c - 1];
+                }
+            }
+            for i in 0..n {
+                let s = (c + 1..n).fold(T::zero(), |acc, j| acc + a[i][j] * v[j - c - 1]) * two;
+                for j in c + 1..n {
+                    a[i][j] = a[i][j] - s * v[j - c - 1];
+                }
+            }
+            for i in c + 2..n {
+                a[i][c] = T::zero();
+            }
+        }
+
+        Ok(a)
+    }
+
+    // Francis double-shift QR iteration on the Hessenberg form, following the
+    // hqr procedure of Wilkinson and Reinsch's Handbook for Automatic
+    // Computation (as in EISPACK). Rows hi + 1.. have converged; each pass
+    // either splits one or two eigenvalues off the bottom of the active block
+    // lo..=hi or applies one implicit double-shift step to it.
+    pub fn eigenvalues(&self) -> Result<Vec<Complex<T>>, LinAlgError> {
+        let mut a = self.hessenberg()?;
+        let (n, _) = a.shape();
+
+        // sum of the Hessenberg entries, for judging negligible subdiagonals
+        let mut norm = T::zero();
+        for i in 0..n {
+            for j in i.saturating_sub(1)..n {
+                norm = norm + a[i][j].abs();
+            }
+        }
+
+        let mut values = Vec::with_capacity(n);
+        // total of the exceptional shifts subtracted from the diagonal
+        let mut shift = T::zero();
+        let mut end = n;
+        while end > 0 {
+            let hi = end - 1;
+            let mut iterations = 0;
+            loop {
+                let lo = block_start(&mut a, hi, norm);
+                if lo == hi {
+                    values.push(Complex::new(a[hi][hi] + shift, T::zero()));
+                    end -= 1;
+                    break;
+                }
+                if lo + 1 == hi {
+                    let (first, second) = trailing_pair(&a, hi, shift);
+                    values.push(first);
+                    values.push(second);
+                    end -= 2;
+                    break;
+                }
+
+                if iterations == MAX_ITERATIONS {
+                    return Err(LinAlgError::NoConvergence {
+                        iterations: MAX_ITERATIONS,
+                    });
+                }
+
+                // the shifts are the eigenvalues of the trailing 2 x 2 block,
+                // given by its diagonal and the product of its off-diagonal
+                let mut last = a[hi][hi];
+                let mut prev = a[hi - 1][hi - 1];
+                let mut coupling = a[hi][hi - 1] * a[hi - 1][hi];
+                if iterations == 10 || iterations == 20 {
+                    // an exceptional shift to break a cycle
+                    shift = shift + last;
+                    for i in 0..=hi {
+                        a[i][i] = a[i][i] - last;
+                    }
+                    let s = a[hi][hi - 1].abs() + a[hi - 1][hi - 2].abs();
+                    last = T::from_f64(0.75) * s;
+                    prev = last;
+                    coupling = T::from_f64(-0.4375) * s * s;
+                }
+                iterations += 1;
+
+                double_shift_step(&mut a, lo, hi, (last, prev, coupling));
+            }
+        }
+
+        values.sort_by(|a, b| b.re.total_cmp(&a.re).then(b.im.total_cmp(&a.im)));
+        Ok(values)
+    }
+
+    pub fn eigen(&self) -> Result<Eigen<T>, LinAlgError> {
+        let values = self.eigenvalues()?;
+        let (n, _) = self.shape();
+
+        // √ε rather than ε, since a defective eigenvalue of multiplicity k
+        // only comes back to about ε^(1/k). The scale is floored at 1 so that
+        // tiny matrices still group rounding noise.
+        let norm = self.norm_inf();
+        let scale = if norm > T::one() { norm } else { T::one() };
+        let tol = Tolerance::new(T::zero(), T::epsilon().sqrt()).threshold(scale);
+
+        // group numerically equal eigenvalues
+        let mut groups: Vec<Vec<Complex<T>>> = Vec::new();
+        for &value in &values {
+            match groups
+                .iter_mut()
+                .find(|group| modulus(group[0] - value) <= tol)
+            {
+                Some(group) => group.push(value),
+                None => groups.push(vec![value]),
+            }
+        }
+
+        let spaces = groups
+            .into_iter()
+            .map(|group| {
+                let count = T::from_usize(group.len());
+                let value = group
+                    .iter()
+                    .fold(Complex::new(T::zero(), T::zero()), |acc, x| acc + x)
+                    / count;
+
+                let mut shifted = self.to_complex();
+                for i in 0..n {
+                    shifted[i][i] = shifted[i][i] - value;
+                }
+
+                EigenSpace {
+                    value,
+                    algebraic_multiplicity: group.len(),
+                    vectors: null_space(shifted, tol),
+                }
+            })
+            .collect();
+
+        Ok(Eigen { values, spaces })
+    }
+}
+
+// Only entries of modulus above the threshold are not zero
+struct Modulus<T>(T);
+
+impl<T: RealField> ZeroTest<Complex<T>> for Modulus<T> {
+    fn is_zero(&self, z: &Complex<T>, _: usize) -> bool {
+        modulus(*z) <= self.0
+    }
+}
+
+// Partial pivoting by modulus, since complex numbers have no order
+fn largest_modulus<T: RealField>(
+    mat: &Mat2d<Complex<T>>,
+    i: usize,
+    cols: Range<usize>,
+    zero: &dyn ZeroTest<Complex<T>>,
+) -> Option<(usize, usize)> {
+    let col = pivot_column(mat, i, cols, zero)?;
+    let row = best_row(mat, i, col, zero, |x, y| modulus(*x) > modulus(*y));
+    Some((row, col))
+}
+
+// Gauss-Jordan treating entries of modulus up to tol as zero
+fn null_space<T: RealField>(a: Mat2d<Complex<T>>, tol: T) -> Vec<vec::Vec<Complex<T>>> {
+    let (_, n) = a.shape();
+    let trace = a.gauss_jordan(largest_modulus, &mut Modulus(tol), n, |_, _, _| {});
+    kernel_basis(&trace.rref, &trace.pivots, n)
+}
+
+// The first row of the unreduced block ending at row hi, zeroing the
+// negligible subdiagonal entry above it
+fn block_start<T: RealField>(a: &mut Mat2d<T>, hi: usize, norm: T) -> usize {
+    let mut lo = hi;
+    while lo > 0 {
+        let mut s = a[lo - 1][lo - 1].abs() + a[lo][lo].abs();
+        if s == T::zero() {
+            s = norm;
+        }
+        if a[lo][lo - 1].abs() + s == s {
+            a[lo][lo - 1] = T::zero();
+            break;
+        }
+        lo -= 1;
+    }
+    lo
+}
+
+// The eigenvalues of the trailing 2 x 2 block ending at row hi, a real pair or
+// a conjugate pair
+fn trailing_pair<T: RealField>(a: &Mat2d<T>, hi: usize, shift: T) -> (Complex<T>, Complex<T>) {
+    let (last, prev) = (a[hi][hi], a[hi - 1][hi - 1]);
+    let coupling = a[hi][hi - 1] * a[hi - 1][hi];
+    let p = T::from_f64(0.5) * (prev - last);
+    let discriminant = p * p + coupling;
+    let root = discriminant.abs().sqrt();
+    let base = last + shift;
+    if discriminant >= T::zero() {
+        // the larger root directly and the smaller from their product, to
+        // avoid cancellation
+        let z = p + root.copysign(p);
+        let larger = Complex::new(base + z, T::zero());
+        if z == T::zero() {
+            (larger, larger)
+        } else {
+            (larger, Complex::new(base - coupling / z, T::zero()))
+        }
+    } else {
+        (Complex::new(base + p, -root), Complex::new(base + p, root))
+    }
+}
+
+// One implicit double-shift QR step on rows and columns lo..=hi. The step
+// starts at the lowest row where two consecutive small subdiagonal entries
+// decouple the block, then chases the bulge down with 3 x 3 Householder
+// reflections.
+fn double_shift_step<T: RealField>(
+    a: &mut Mat2d<T>,
+    lo: usize,
+    hi: usize,
+    (last, prev, coupling): (T, T, T),
+) {
+    // (p, q, r) is the first column of (H - s1 I)(H - s2 I) from row start,
+    // scaled to avoid overflow
+    let mut start = hi - 2;
+    let (mut p, mut q, mut r);
+    loop {
+        let z = a[start][start];
+        let (dl, dp) = (last - z, prev - z);
+        p = (dl * dp - coupling) / a[start + 1][start] + a[start][start + 1];
+        q = a[start + 1][start + 1] - z - dl - dp;
+        r = a[start + 2][start + 1];
+        let s = p.abs() + q.abs() + r.abs();
+        p = p / s;
+        q = q / s;
+        r = r / s;
+        if start == lo {
+            break;
+        }
+        let off = a[start][start - 1].abs() * (q.abs() + r.abs());
+        let diag =
+            p.abs() * (a[start - 1][start - 1].abs() + z.abs() + a[start + 1][start + 1].abs());
+        if off + diag == diag {
+            break;
+        }
+        start -= 1;
+    }
+
+    for i in start + 2..=hi {
+        a[i][i - 2] = T::zero();
+        if i != start + 2 {
+            a[i][i - 3] = T::zero();
+        }
+    }
+
+    let mut scale = T::zero();
+    for k in start..hi {
+        // rows k + 2.. of the reflection vanish on the last step
+        let full = k + 1 != hi;
+        if k != start {
+            p = a[k][k - 1];
+            q = a[k + 1][k - 1];
+            r = if full { a[k + 2][k - 1] } else { T::zero() };
+            scale = p.abs() + q.abs() + r.abs();
+            if scale != T::zero() {
+                p = p / scale;
+                q = q / scale;
+                r = r / scale;
+            }
+        }
+
+        let s = (p * p + q * q + r * r).sqrt().copysign(p);
+        if s == T::zero() {
+            continue;
+        }
+        if k == start {
+            if lo != start {
+                a[k][k - 1] = -a[k][k - 1];
+            }
+        } else {
+            a[k][k - 1] = -s * scale;
+        }
+
+        // the reflection is I - u vᵀ with u = (x, y, z) and v = (1, q, r)
+        p = p + s;
+        let (x, y, z) = (p / s, q / s, r / s);
+        let (q, r) = (q / p, r / p);
+
+        for j in k..=hi {
+            let mut t = a[k][j] + q * a[k + 1][j];
+            if full {
+                t = t + r * a[k + 2][j];
+                a[k + 2][j] = a[k + 2][j] - t * z;
+            }
+            a[k + 1][j] = a[k + 1][j] - t * y;
+            a[k][j] = a[k][j] - t * x;
+        }
+
+        for i in lo..=hi.min(k + 3) {
+            let mut t = x * a[i][k] + y * a[i][k + 1];
+            if full {
+                t = t + z * a[i][k + 2];
+                a[i][k + 2] = a[i][k + 2] - t * r;
+            }
+            a[i][k + 1] = a[i][k + 1] - t * q;
+            a[i][k] = a[i][k] - t;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eigen(rows: &[&[f64]]) -> (Mat2d<f64>, Eigen<f64>) {
+        let a = Mat2d::from(
+            rows.iter()
+                .map(|row| vec::Vec::from(row.to_vec()))
+                .collect::<Vec<_>>(),
+        );
+        let eigen = a.eigen().unwrap();
+        (a, eigen)
+    }
+
+    fn close(z: Complex<f64>, w: Complex<f64>) -> bool {
+        modulus(z - w) < 1e-9
+    }
+
+    // A v = λ v for every basis vector of every eigenspace
+    fn assert_eigenvectors(a: &Mat2d<f64>, eigen: &Eigen<f64>) {
+        let a = a.to_complex();
+        let (n, _) = a.shape();
+        for space in &eigen.spaces {
+            for v in &space.vectors {
+                assert!(v.norm() > 0.5);
+                for i in 0..n {
+                    let av = (0..n).fold(Complex::new(0.0, 0.0), |acc, j| acc + a[i][j] * v[j]);
+                    assert!(close(av, space.value * v[i]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rotation_has_complex_eigenvalues() {
+        let (a, eigen) = eigen(&[&[0.0, -1.0], &[1.0, 0.0]]);
+        assert!(close(eigen.values[0], Complex::new(0.0, 1.0)));
+        assert!(close(eigen.values[1], Complex::new(0.0, -1.0)));
+        assert!(eigen.is_diagonalizable());
+        assert_eigenvectors(&a, &eigen);
+    }
+
+    #[test]
+    fn jordan_block_is_defective() {
+        let (a, eigen) = eigen(&[&[2.0, 1.0], &[0.0, 2.0]]);
+        assert_eq!(eigen.spaces.len(), 1);
+        let space = &eigen.spaces[0];
+        assert!(close(space.value, Complex::new(2.0, 0.0)));
+        assert_eq!(space.algebraic_multiplicity, 2);
+        assert_eq!(space.geometric_multiplicity(), 1);
+        assert!(!eigen.is_diagonalizable());
+        assert_eigenvectors(&a, &eigen);
+    }
+
+    #[test]
+    fn symmetric_matrix_has_real_eigenvalues() {
+        let (a, eigen) = eigen(&[&[2.0, 1.0, 0.0], &[1.0, 2.0, 0.0], &[0.0, 0.0, 1.0]]);
+        let expected = [3.0, 1.0, 1.0].map(|x| Complex::new(x, 0.0));
+        assert!(eigen.values.iter().zip(expected).all(|(&z, w)| close(z, w)));
+        assert_eq!(eigen.spaces.len(), 2);
+        assert_eq!(eigen.spaces[1].geometric_multiplicity(), 2);
+        assert_eigenvectors(&a, &eigen);
+    }
+
+    #[test]
+    fn non_finite_entries_do_not_panic() {
+        let a = Mat2d::from(vec![
+            vec::Vec::from([f64::NAN, 1.0]),
+            vec::Vec::from([1.0, 0.0]),
+        ]);
+        let _ = a.eigenvalues();
+    }
+}
@@ -0,0 +1,179 @@
+use super::{
+    mat::leading_columns, tolerance::count, Field, LinAlgError, Mat2d, OrderedField, Pivoting,
+    RowOp, RrefTrace, Tolerance,
+};
+
+#[derive(Debug, Clone)]
+pub struct ElementaryFactorization<T> {
+    // The row operations taking A to its RREF, in the order they are applied
+    pub ops: Vec<RowOp<T>>,
+    // E_1, ..., E_k, the elementary matrix of each operation
+    pub matrices: Vec<Mat2d<T>>,
+    // E_k ⋯ E_1
+    pub product: Mat2d<T>,
+    pub rref: Mat2d<T>,
+}
+
+impl<T: Field> ElementaryFactorization<T> {
+    // E_k ⋯ E_1 A = R
+    pub fn verify(&self, a: &Mat2d<T>) -> bool {
+        (&self.product * a).is_ok_and(|r| r == self.rref)
+    }
+
+    // E_k ⋯ E_1 A = R up to rounding, so that f64 inputs can be checked. Each
+    // of the k operations and each term of the product can add an error of
+    // order ‖E_k ⋯ E_1‖ ‖A‖, giving a scale of (k + max(m, n)) times that.
+    pub fn verify_approx(&self, a: &Mat2d<T>, tol: &Tolerance<T>) -> bool
+    where
+        T: OrderedField,
+    {
+        let Ok(r) = &self.product * a else {
+            return false;
+        };
+        if r.shape() != self.rref.shape() {
+            return false;
+        }
+        let (m, n) = a.shape();
+        let scale = count::<T>(self.ops.len() + m.max(n)) * self.product.norm_inf() * a.norm_inf();
+        r.iter().zip(self.rref.iter()).all(|(u, v)| {
+            u.iter()
+                .zip(v.iter())
+                .all(|(x, y)| tol.is_negligible(&(x.clone() - y.clone()), scale.clone()))
+        })
+    }
+}
+
+// The elementary matrices of ops acting on matrices with m rows, in order
+pub fn elementary_matrices<'a, T, I>(ops: I, m: usize) -> Vec<Mat2d<T>>
+where
+    T: Field + 'a,
+    I: IntoIterator<Item = &'a RowOp<T>>,
+{
+    ops.into_iter().map(|op| op.elementary_matrix(m)).collect()
+}
+
+impl<T: Field> Mat2d<T> {
+    pub fn elementary_factorization(&self) -> ElementaryFactorization<T> {
+        factorization(self, self.rref_verbose())
+    }
+
+    // Complete pivoting would factor AQ rather than A, so it searches one
+    // column at a time here, which is partial pivoting
+    pub fn elementary_factorization_with(
+        &self,
+        pivoting: impl Into<Pivoting<T>>,
+    ) -> ElementaryFactorization<T>
+    where
+        T: OrderedField,
+    {
+        let pivoting = pivoting.into().in_column_order();
+        factorization(self, self.rref_with(pivoting))
+    }
+
+    // A = E_1⁻¹ E_2⁻¹ ⋯ E_k⁻¹, since E_k ⋯ E_1 A = I for invertible A
+    pub fn elementary_product(&self) -> Result<Vec<Self>, LinAlgError> {
+        inverse_product(self, Self::elementary_factorization)
+    }
+
+    pub fn elementary_product_with(
+        &self,
+        pivoting: impl Into<Pivoting<T>>,
+    ) -> Result<Vec<Self>, LinAlgError>
+    where
+        T: OrderedField,
+    {
+        let pivoting = pivoting.into();
+        inverse_product(self, |a| a.elementary_factorization_with(pivoting))
+    }
+}
+
+fn factorization<T: Field>(a: &Mat2d<T>, trace: RrefTrace<T>) -> ElementaryFactorization<T> {
+    let (m, _) = a.shape();
+    let ops: Vec<RowOp<T>> = trace.steps.into_iter().map(|(_, op, _)| op).collect();
+
+    // applying each operation to the identity accumulates E_k ⋯ E_1
+    let mut product = Mat2d::identity(m, m).unwrap();
+    for op in &ops {
+        op.apply(&mut product);
+    }
+
+    ElementaryFactorization {
+        matrices: elementary_matrices(&ops, m),
+        ops,
+        product,
+        rref: trace.rref,
+    }
+}
+
+fn inverse_product<T: Field, F>(a: &Mat2d<T>, factor: F) -> Result<Vec<Mat2d<T>>, LinAlgError>
+where
+    F: FnOnce(&Mat2d<T>) -> ElementaryFactorization<T>,
+{
+    let (m, n) = a.shape();
+    if m != n {
+        return Err(LinAlgError::NotSquare { shape: (m, n) });
+    }
+
+    let factorization = factor(a);
+    let pivots = leading_columns(&factorization.rref);
+    if let Some(col) = (0..n).find(|col| !pivots.contains(col)) {
+        return Err(LinAlgError::Singular { col });
+    }
+
+    let inverses: Vec<RowOp<T>> = factorization.ops.iter().map(RowOp::inverse).collect();
+    Ok(elementary_matrices(&inverses, n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{rational::rational, vec, PivotStrategy};
+    use super::*;
+
+    #[test]
+    fn factorization_with_keeps_the_column_order() {
+        let a = Mat2d::from(vec![
+            vec::Vec::from([1.0, 2.0, 3.0]),
+            vec::Vec::from([4.0, 5.0, 6.0]),
+            vec::Vec::from([7.0, 8.0, 10.0]),
+        ]);
+        for strategy in [PivotStrategy::Partial, PivotStrategy::Complete] {
+            let pivoting = Pivoting::new(strategy, Tolerance::default());
+            let factorization = a.elementary_factorization_with(pivoting.clone());
+            assert!(factorization.verify_approx(&a, &Tolerance::default()));
+            assert_eq!(
+                a.elementary_product_with(pivoting).unwrap().len(),
+                factorization.ops.len()
+            );
+        }
+    }
+
+    #[test]
+    fn factorization_reduces_the_matrix() {
+        let a = Mat2d::from([[0, 2, 4], [1, 1, 1], [2, 2, 2]]).map(|&x| rational(x, 1));
+        let factorization = a.elementary_factorization();
+        assert!(factorization.verify(&a));
+        assert_eq!(factorization.rref, a.rref());
+        assert_eq!(factorization.matrices.len(), factorization.ops.len());
+        let product = factorization
+            .matrices
+            .iter()
+            .fold(Mat2d::identity(3, 3).unwrap(), |acc, e| (e * &acc).unwrap());
+        assert_eq!(product, factorization.product);
+    }
+
+    #[test]
+    fn invertible_matrix_is_a_product_of_elementary_matrices() {
+        let a = Mat2d::from([[0, 1], [2, 3]]).map(|&x| rational(x, 1));
+        let factors = a.elementary_product().unwrap();
+        let product = factors
+            .iter()
+            .fold(Mat2d::identity(2, 2).unwrap(), |acc, e| (&acc * e).unwrap());
+        assert_eq!(product, a);
+
+        let singular = Mat2d::from([[1, 2], [2, 4]]).map(|&x| rational(x, 1));
+        assert!(matches!(
+            singular.elementary_product(),
+            Err(LinAlgError::Singular { col: 1 })
+        ));
+    }
+}
@@ -0,0 +1,45 @@
+use std::fmt::{self, Display};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinAlgError {
+    DimensionMismatch {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    NotSquare {
+        shape: (usize, usize),
+    },
+    Singular {
+        col: usize,
+    },
+    LinearlyDependent {
+        col: usize,
+    },
+    NoConvergence {
+        iterations: usize,
+    },
+}
+
+impl Display for LinAlgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DimensionMismatch { expected, found } => write!(
+                f,
+                "dimension mismatch: expected {}x{}, found {}x{}",
+                expected.0, expected.1, found.0, found.1
+            ),
+            Self::NotSquare { shape } => {
+                write!(f, "expected a square matrix, found {}x{}", shape.0, shape.1)
+            }
+            Self::Singular { col } => write!(f, "matrix is singular: no pivot in column {col}"),
+            Self::LinearlyDependent { col } => {
+                write!(f, "column {col} depends linearly on the previous columns")
+            }
+            Self::NoConvergence { iterations } => {
+                write!(f, "iteration did not converge after {iterations} steps")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LinAlgError {}
@@ -1,87 +1,328 @@
-use std::ops::{Div, Mul, Sub};
+use super::{
+    mat::kernel_basis,
+    pivot::{eliminate, first_nonzero, place_pivot, swap_pivot_row, unpermute, Chooser},
+    tolerance::{Exact, ZeroTest},
+    vec, Field, LinAlgError, Mat2d, OrderedField, Pivoting, RowOp,
+};
 
-use num_traits::identities;
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolutionSet<T> {
+    Unique(vec::Vec<T>),
+    // Row of the reduced augmented matrix that reads 0 = c with c != 0
+    None {
+        inconsistent_row: usize,
+    },
+    // x = particular + Σ t_i basis[i], one parameter per free variable
+    Infinite {
+        particular: vec::Vec<T>,
+        free_vars: Vec<usize>,
+        basis: Vec<vec::Vec<T>>,
+    },
+}
+
+impl<T: Field> SolutionSet<T> {
+    pub fn is_consistent(&self) -> bool {
+        !matches!(self, Self::None { .. })
+    }
+
+    // The solution for the given values of the free variables
+    pub fn point(&self, params: &[T]) -> Option<vec::Vec<T>> {
+        match self {
+            Self::Unique(x) => Some(x.clone()),
+            Self::None { .. } => None,
+            Self::Infinite {
+                particular, basis, ..
+            } => {
+                assert!(
+                    params.len() == basis.len(),
+                    "expected one parameter per free variable"
+                );
+                let mut x = particular.clone();
+                for (t, v) in params.iter().zip(basis) {
+                    x.axpy(t.clone(), v);
+                }
+                Some(x)
+            }
+        }
+    }
+}
+
+impl<T: Field> Mat2d<T> {
+    pub fn solve(&self, b: &vec::Vec<T>) -> Result<SolutionSet<T>, LinAlgError> {
+        let augmented = self.augment(b)?;
+        Ok(augmented.solve_augmented(first_nonzero, &mut Exact))
+    }
+
+    // With a tolerance, a residue like 0 = 1e-16 left in b's column does not
+    // make the system inconsistent. Complete pivoting only moves the columns
+    // of A, and the solutions come back in the original variable order.
+    pub fn solve_with(
+        &self,
+        b: &vec::Vec<T>,
+        pivoting: impl Into<Pivoting<T>>,
+    ) -> Result<SolutionSet<T>, LinAlgError>
+    where
+        T: OrderedField,
+    {
+        let pivoting = pivoting.into();
+        let augmented = self.augment(b)?;
+        let mut zero = pivoting.zero_test(&augmented);
+        Ok(augmented.solve_augmented(pivoting.strategy.chooser(), &mut zero))
+    }
+
+    // Solves [A | b], pivoting in the columns of A only. The augmented matrix
+    // has b's column even when A is 0 x 0, whose system has the empty vector
+    // as its unique solution.
+    fn solve_augmented(&self, choose: Chooser<T>, zero: &mut dyn ZeroTest<T>) -> SolutionSet<T> {
+        let (_, cols) = self.shape();
+        let n = cols
+            .checked_sub(1)
+            .expect("an augmented matrix has b's column");
+        let trace = self.gauss_jordan(choose, zero, n, |_, _, _| {});
+        solution_set(&trace.rref, &trace.pivots, &trace.col_permutation[..n])
+    }
+}
+
+// Reads the solutions off the RREF of [AQ | b], where column k of AQ is column
+// permutation[k] of A, and the pivot columns of AQ
+fn solution_set<T: Field>(
+    rref: &Mat2d<T>,
+    pivots: &[usize],
+    permutation: &[usize],
+) -> SolutionSet<T> {
+    let n = permutation.len();
+    // a row below the pivots that still has a non-zero in b's column reads
+    // 0 = c
+    if let Some(row) = (pivots.len()..rref.shape().0).find(|&row| !rref[row][n].is_zero()) {
+        return SolutionSet::None {
+            inconsistent_row: row,
+        };
+    }
 
-use super::Mat2d;
+    let mut particular = vec::Vec::zeros(n);
+    for (row, &col) in pivots.iter().enumerate() {
+        particular[col] = rref[row][n].clone();
+    }
 
-// pub enum State {
-//     Unsolvable,
-//     Infinite,
-//     Solvable,
-// }
+    if pivots.len() == n {
+        return SolutionSet::Unique(unpermute(&particular, permutation));
+    }
 
-#[derive(Debug)]
-pub enum Step<T> {
-    Swap { from: usize, to: usize },
-    Sub { scale: T, from: usize, to: usize },
+    let free: Vec<usize> = (0..n).filter(|col| !pivots.contains(col)).collect();
+    SolutionSet::Infinite {
+        particular: unpermute(&particular, permutation),
+        free_vars: free.iter().map(|&col| permutation[col]).collect(),
+        basis: kernel_basis(rref, pivots, n)
+            .iter()
+            .map(|v| unpermute(v, permutation))
+            .collect(),
+    }
 }
 
 pub struct GaussElimIter<T> {
     mat: Mat2d<T>,
+    choose: Chooser<T>,
+    zero: Box<dyn ZeroTest<T>>,
+    col_permutation: Vec<usize>,
+    // the row the next pivot goes to and the column to search from
+    pivot_row: usize,
     col: usize,
+    // column of the current pivot, once it is in place
+    pivot: Option<usize>,
+    // row the current pivot is still to be swapped up from
+    pivot_from: Option<usize>,
     row: usize,
 }
 
-impl<T> From<Mat2d<T>> for GaussElimIter<T> {
+impl<T: Field> From<Mat2d<T>> for GaussElimIter<T> {
     fn from(mat: Mat2d<T>) -> Self {
+        Self::new(mat, first_nonzero, Box::new(Exact))
+    }
+}
+
+impl<T: Field> GaussElimIter<T> {
+    fn new(mat: Mat2d<T>, choose: Chooser<T>, zero: Box<dyn ZeroTest<T>>) -> Self {
+        let (_, n) = mat.shape();
         Self {
             mat,
+            choose,
+            zero,
+            col_permutation: (0..n).collect(),
+            pivot_row: 0,
             col: 0,
-            row: 1,
+            pivot: None,
+            pivot_from: None,
+            row: 0,
         }
     }
+
+    // With a tolerance, pivot candidates and computed entries within it of
+    // their column's scale count as zero, and the computed ones are snapped
+    // in each yielded matrix
+    pub fn with_strategy(mat: Mat2d<T>, pivoting: impl Into<Pivoting<T>>) -> Self
+    where
+        T: OrderedField + 'static,
+    {
+        let pivoting = pivoting.into();
+        let zero = Box::new(pivoting.zero_test(&mat));
+        Self::new(mat, pivoting.strategy.chooser(), zero)
+    }
+
+    // Column j of the yielded matrices is column col_permutation()[j] of the
+    // original. Only complete pivoting moves columns.
+    pub fn col_permutation(&self) -> &[usize] {
+        &self.col_permutation
+    }
 }
 
 impl<T> Iterator for GaussElimIter<T>
 where
-    T: identities::Zero + PartialEq + Div<Output = T> + Mul<Output = T> + Sub<Output = T> + Copy,
+    T: Field,
 {
-    type Item = (Step<T>, Mat2d<T>);
+    type Item = (RowOp<T>, Mat2d<T>);
     fn next(&mut self) -> Option<Self::Item> {
         let (m, n) = self.mat.shape();
 
         loop {
-            if self.col >= m || self.row >= n {
-                return None;
-            }
+            let i = self.pivot_row;
+            let col = match self.pivot {
+                Some(col) => col,
+                None => {
+                    if i >= m {
+                        return None;
+                    }
+                    let (row, col, col_swap) = place_pivot(
+                        &mut self.mat,
+                        i,
+                        self.col,
+                        n,
+                        self.choose,
+                        self.zero.as_mut(),
+                        &mut self.col_permutation,
+                    )?;
+                    self.pivot = Some(col);
+                    self.pivot_from = Some(row);
+                    self.row = i + 1;
+                    if let Some(swap) = col_swap {
+                        return Some((swap, self.mat.clone()));
+                    }
+                    col
+                }
+            };
 
-            if self.mat[self.col][self.col] == T::zero() {
-                // There exists a non-zero pivot in the ith column
-                if let Some(idx) = self.mat.iter().position(|x| !T::zero().eq(&x[self.col])) {
-                    self.mat.swap_row(self.col, idx);
-                    return Some((
-                        Step::Swap {
-                            from: self.col,
-                            to: idx,
-                        },
-                        self.mat.clone(),
-                    ));
-                } else {
-                    self.col += 1;
+            // the row swap after a column swap is a step of its own
+            if let Some(row) = self.pivot_from.take() {
+                if let Some(swap) = swap_pivot_row(&mut self.mat, i, row) {
+                    return Some((swap, self.mat.clone()));
                 }
             }
 
-            loop {
-                if self.mat[self.col][self.row] != T::zero() {
-                    let scale = self.mat[self.row][self.col] / self.mat[self.col][self.col];
-                    self.mat[self.row] = self.mat[self.row].sub(&self.mat[self.col].mul(scale));
+            while self.row < m {
+                let row = self.row;
+                self.row += 1;
+
+                if !self.mat[row][col].is_zero() {
+                    let scale = eliminate(&mut self.mat, i, row, col, self.zero.as_mut());
                     return Some((
-                        Step::Sub {
+                        RowOp::AddMultiple {
                             scale,
-                            from: self.col,
-                            to: self.row,
+                            from: i,
+                            to: row,
                         },
                         self.mat.clone(),
                     ));
                 }
+            }
 
-                self.row += 1;
-                if self.row == n {
-                    self.col += 1;
-                    self.row = self.col + 1;
-                    break;
-                }
+            self.pivot_row += 1;
+            self.col = col + 1;
+            self.pivot = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::{rational::rational, vec, Mat2d, PivotStrategy, Rational, SolutionSet};
+
+    fn mat(rows: &[&[i64]]) -> Mat2d<Rational> {
+        Mat2d::from(
+            rows.iter()
+                .map(|row| row.iter().map(|&x| rational(x, 1)).collect())
+                .collect::<Vec<vec::Vec<Rational>>>(),
+        )
+    }
+
+    fn col(xs: &[i64]) -> vec::Vec<Rational> {
+        xs.iter().map(|&x| rational(x, 1)).collect()
+    }
+
+    #[test]
+    fn unique_solution() {
+        let a = mat(&[&[2, 1], &[1, 3]]);
+        let solution = a.solve(&col(&[3, 5])).unwrap();
+        assert_eq!(
+            solution,
+            SolutionSet::Unique(vec::Vec::from([rational(4, 5), rational(7, 5)]))
+        );
+    }
+
+    #[test]
+    fn inconsistent_system() {
+        let a = mat(&[&[1, 2], &[2, 4]]);
+        let solution = a.solve(&col(&[1, 3])).unwrap();
+        assert_eq!(
+            solution,
+            SolutionSet::None {
+                inconsistent_row: 1
             }
+        );
+        assert!(!solution.is_consistent());
+        assert_eq!(solution.point(&[]), None);
+    }
+
+    #[test]
+    fn parametric_solution() {
+        let a = mat(&[&[1, 2, 1], &[2, 4, 0]]);
+        let b = col(&[3, 2]);
+        let solution = a.solve(&b).unwrap();
+        let SolutionSet::Infinite {
+            ref free_vars,
+            ref basis,
+            ..
+        } = solution
+        else {
+            panic!("expected infinitely many solutions, got {solution:?}");
+        };
+        assert_eq!(free_vars, &[1]);
+        assert_eq!(basis.len(), 1);
+        for t in [-2, 0, 5] {
+            let x = solution.point(&[rational(t, 1)]).unwrap();
+            assert_eq!((&a * &x).unwrap(), b);
         }
     }
+
+    #[test]
+    fn complete_pivoting_keeps_variable_order() {
+        let a = mat(&[&[1, 5, 2], &[2, 0, 1]]);
+        let b = col(&[8, 3]);
+        let solution = a.solve_with(&b, PivotStrategy::Complete).unwrap();
+        for t in [-1, 0, 3] {
+            let x = solution.point(&[rational(t, 1)]).unwrap();
+            assert_eq!((&a * &x).unwrap(), b);
+        }
+    }
+
+    #[test]
+    fn empty_system() {
+        let a = Mat2d::<f64>::zeros(0, 0);
+        let solution = a.solve(&vec::Vec::zeros(0)).unwrap();
+        assert_eq!(solution, SolutionSet::Unique(vec::Vec::zeros(0)));
+    }
+
+    #[test]
+    fn wrong_length_right_hand_side() {
+        let a = mat(&[&[1, 0], &[0, 1]]);
+        assert!(a.solve(&col(&[1, 2, 3])).is_err());
+    }
 }
@@ -0,0 +1,570 @@
+use std::{
+    fmt::{self, Debug, Display},
+    iter::FromIterator,
+    ops::{Add, BitXorAssign, Div, Index, Mul, Neg, Sub},
+};
+
+use num_traits::identities::{One, Zero};
+
+use super::{Field, LinAlgError, Mat2d, Ring};
+
+// Integers modulo a prime P. Every way of making an element checks at compile
+// time that P is prime, since otherwise division is not defined.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Gf<const P: u64>(u64);
+
+impl<const P: u64> Gf<P> {
+    const PRIME: () = assert!(is_prime(P), "GF(P) needs a prime P");
+
+    pub fn new(x: i64) -> Self {
+        let () = Self::PRIME;
+        Self((x as i128).rem_euclid(P as i128) as u64)
+    }
+
+    pub fn value(self) -> u64 {
+        self.0
+    }
+
+    pub fn pow(self, mut e: u64) -> Self {
+        let (mut base, mut acc) = (self, Self::one());
+        while e > 0 {
+            if e & 1 == 1 {
+                acc = acc * base;
+            }
+            base = base * base;
+            e >>= 1;
+        }
+        acc
+    }
+}
+
+impl<const P: u64> From<u64> for Gf<P> {
+    fn from(x: u64) -> Self {
+        let () = Self::PRIME;
+        Self(x % P)
+    }
+}
+
+impl<const P: u64> Add for Gf<P> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(((self.0 as u128 + rhs.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Sub for Gf<P> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<const P: u64> Mul for Gf<P> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(((self.0 as u128 * rhs.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Div for Gf<P> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        assert!(rhs.0 != 0, "division by zero in GF({P})");
+        // Fermat: a^(p - 2) = a^(-1)
+        self * rhs.pow(P - 2)
+    }
+}
+
+impl<const P: u64> Neg for Gf<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        if self.0 == 0 {
+            self
+        } else {
+            Self(P - self.0)
+        }
+    }
+}
+
+impl<const P: u64> Zero for Gf<P> {
+    fn zero() -> Self {
+        let () = Self::PRIME;
+        Self(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl<const P: u64> One for Gf<P> {
+    fn one() -> Self {
+        let () = Self::PRIME;
+        Self(1)
+    }
+}
+
+impl<const P: u64> Default for Gf<P> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+// Miller-Rabin with the first twelve primes as witnesses, which is exact for
+// every u64
+const fn is_prime(n: u64) -> bool {
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    let mut i = 0;
+    while i < WITNESSES.len() {
+        if n.is_multiple_of(WITNESSES[i]) {
+            return n == WITNESSES[i];
+        }
+        i += 1;
+    }
+
+    // n - 1 = d 2^s with d odd
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    let mut i = 0;
+    while i < WITNESSES.len() {
+        let mut x = pow_mod(WITNESSES[i], d, n);
+        let mut r = 1;
+        let mut composite = x != 1 && x != n - 1;
+        while composite && r < s {
+            x = mul_mod(x, x, n);
+            composite = x != n - 1;
+            r += 1;
+        }
+        if composite {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn mul_mod(a: u64, b: u64, n: u64) -> u64 {
+    (a as u128 * b as u128 % n as u128) as u64
+}
+
+const fn pow_mod(mut base: u64, mut e: u64, n: u64) -> u64 {
+    let mut acc = 1;
+    base %= n;
+    while e > 0 {
+        if e & 1 == 1 {
+            acc = mul_mod(acc, base, n);
+        }
+        base = mul_mod(base, base, n);
+        e >>= 1;
+    }
+    acc
+}
+
+impl<const P: u64> Ring for Gf<P> {}
+impl<const P: u64> Field for Gf<P> {}
+
+impl<const P: u64> Display for Gf<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const P: u64> Debug for Gf<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+const WORD: usize = u64::BITS as usize;
+
+// A row over GF(2) packed 64 entries per word, so that adding two rows is
+// a word-wide XOR
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Gf2Row {
+    len: usize,
+    words: Vec<u64>,
+}
+
+impl Gf2Row {
+    pub fn zeros(len: usize) -> Self {
+        Self {
+            len,
+            words: vec![0; len.div_ceil(WORD)],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> bool {
+        assert!(
+            i < self.len,
+            "index {i} out of bounds for row of length {}",
+            self.len
+        );
+        self.words[i / WORD] >> (i % WORD) & 1 == 1
+    }
+
+    pub fn set(&mut self, i: usize, bit: bool) {
+        assert!(
+            i < self.len,
+            "index {i} out of bounds for row of length {}",
+            self.len
+        );
+        let mask = 1 << (i % WORD);
+        if bit {
+            self.words[i / WORD] |= mask;
+        } else {
+            self.words[i / WORD] &= !mask;
+        }
+    }
+
+    pub fn flip(&mut self, i: usize) {
+        assert!(
+            i < self.len,
+            "index {i} out of bounds for row of length {}",
+            self.len
+        );
+        self.words[i / WORD] ^= 1 << (i % WORD);
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    // Index of the first set bit at or after from
+    pub fn first_one(&self, from: usize) -> Option<usize> {
+        if from >= self.len {
+            return None;
+        }
+        let start = from / WORD;
+        let first = self.words[start] & (!0 << (from % WORD));
+        if first != 0 {
+            return Some(start * WORD + first.trailing_zeros() as usize);
+        }
+        self.words[start + 1..]
+            .iter()
+            .position(|&w| w != 0)
+            .map(|k| (start + 1 + k) * WORD + self.words[start + 1 + k].trailing_zeros() as usize)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(|i| self.get(i))
+    }
+
+    // self ^= other, skipping the first start words (known to be zero in other)
+    fn xor_from(&mut self, other: &Self, start: usize) {
+        for (a, b) in self.words[start..].iter_mut().zip(&other.words[start..]) {
+            *a ^= b;
+        }
+    }
+}
+
+impl BitXorAssign<&Gf2Row> for Gf2Row {
+    fn bitxor_assign(&mut self, rhs: &Gf2Row) {
+        assert!(
+            self.len == rhs.len,
+            "two rows in addition must have the same length"
+        );
+        self.xor_from(rhs, 0);
+    }
+}
+
+impl FromIterator<bool> for Gf2Row {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let bits: Vec<bool> = iter.into_iter().collect();
+        let mut row = Self::zeros(bits.len());
+        for (i, _) in bits.iter().enumerate().filter(|(_, &bit)| bit) {
+            row.set(i, true);
+        }
+        row
+    }
+}
+
+impl Debug for Gf2Row {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bits: String = self.iter().map(|b| if b { '1' } else { '0' }).collect();
+        write!(f, "[{bits}]")
+    }
+}
+
+// Matrix over GF(2) stored as packed rows
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Gf2Mat {
+    rows: Vec<Gf2Row>,
+    cols: usize,
+}
+
+impl Gf2Mat {
+    pub fn zeros(m: usize, n: usize) -> Self {
+        Self {
+            rows: vec![Gf2Row::zeros(n); m],
+            cols: n,
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut mat = Self::zeros(n, n);
+        for i in 0..n {
+            mat.rows[i].set(i, true);
+        }
+        mat
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows.len(), self.cols)
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> bool {
+        self.rows[i].get(j)
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, bit: bool) {
+        self.rows[i].set(j, bit);
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Gf2Row> {
+        self.rows.iter()
+    }
+
+    pub fn swap_row(&mut self, from: usize, to: usize) {
+        self.rows.swap(from, to);
+    }
+
+    // row[to] += row[from]
+    pub fn add_row(&mut self, from: usize, to: usize) {
+        assert!(from != to, "cannot add a row to itself");
+        let src = self.rows[from].clone();
+        self.rows[to] ^= &src;
+    }
+
+    // Gauss-Jordan elimination, returning the RREF and its pivot columns
+    fn reduce(&self) -> (Self, Vec<usize>) {
+        let mut mat = self.clone();
+        let (m, n) = self.shape();
+        let mut pivots = Vec::new();
+
+        for col in 0..n {
+            let row = pivots.len();
+            if row == m {
+                break;
+            }
+            let Some(idx) = (row..m).find(|&i| mat.rows[i].get(col)) else {
+                continue;
+            };
+            mat.rows.swap(row, idx);
+
+            // entries left of col are already zero in the pivot row
+            let pivot = mat.rows[row].clone();
+            for (i, r) in mat.rows.iter_mut().enumerate() {
+                if i != row && r.get(col) {
+                    r.xor_from(&pivot, col / WORD);
+                }
+            }
+            pivots.push(col);
+        }
+
+        (mat, pivots)
+    }
+
+    pub fn rref(&self) -> Self {
+        self.reduce().0
+    }
+
+    pub fn rank(&self) -> usize {
+        self.reduce().1.len()
+    }
+
+    pub fn inverse(&self) -> Result<Self, LinAlgError> {
+        let (m, n) = self.shape();
+        if m != n {
+            return Err(LinAlgError::NotSquare { shape: (m, n) });
+        }
+
+        // [A | I]
+        let mut augmented = Self::zeros(n, 2 * n);
+        for i in 0..n {
+            for j in (0..n).filter(|&j| self.get(i, j)) {
+                augmented.set(i, j, true);
+            }
+            augmented.set(i, n + i, true);
+        }
+
+        let (reduced, pivots) = augmented.reduce();
+        if let Some(col) = (0..n).find(|&col| pivots.get(col) != Some(&col)) {
+            return Err(LinAlgError::Singular { col });
+        }
+
+        let mut inverse = Self::zeros(n, n);
+        for i in 0..n {
+            for j in 0..n {
+                inverse.set(i, j, reduced.get(i, n + j));
+            }
+        }
+        Ok(inverse)
+    }
+
+    // Basis of {x : Ax = 0}, one vector per free column
+    pub fn null_space(&self) -> Vec<Gf2Row> {
+        let (reduced, pivots) = self.reduce();
+        (0..self.cols)
+            .filter(|col| !pivots.contains(col))
+            .map(|free| {
+                let mut v = Gf2Row::zeros(self.cols);
+                v.set(free, true);
+                for (row, &col) in pivots.iter().enumerate() {
+                    v.set(col, reduced.get(row, free));
+                }
+                v
+            })
+            .collect()
+    }
+}
+
+impl Index<usize> for Gf2Mat {
+    type Output = Gf2Row;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.rows[index]
+    }
+}
+
+impl From<Vec<Gf2Row>> for Gf2Mat {
+    fn from(rows: Vec<Gf2Row>) -> Self {
+        let cols = rows.first().map_or(0, Gf2Row::len);
+        assert!(
+            rows.iter().all(|row| row.len() == cols),
+            "all rows of a matrix must have the same length"
+        );
+        Self { rows, cols }
+    }
+}
+
+impl From<&Mat2d<Gf<2>>> for Gf2Mat {
+    fn from(mat: &Mat2d<Gf<2>>) -> Self {
+        let (_, n) = mat.shape();
+        Self {
+            rows: mat
+                .iter()
+                .map(|row| row.iter().map(|x| !x.is_zero()).collect())
+                .collect(),
+            cols: n,
+        }
+    }
+}
+
+impl From<&Gf2Mat> for Mat2d<Gf<2>> {
+    fn from(mat: &Gf2Mat) -> Self {
+        Mat2d::from(
+            mat.rows
+                .iter()
+                .map(|row| row.iter().map(|b| Gf::from(b as u64)).collect())
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl Debug for Gf2Mat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[")?;
+        for row in &self.rows {
+            writeln!(f, "\t{row:?},")?;
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type F7 = Gf<7>;
+
+    #[test]
+    fn field_arithmetic_mod_p() {
+        assert_eq!(F7::new(-1).value(), 6);
+        assert_eq!(F7::new(3) + F7::new(5), F7::new(1));
+        assert_eq!(F7::new(2) - F7::new(5), F7::new(4));
+        assert_eq!(F7::new(3) * F7::new(5), F7::new(1));
+        assert_eq!(F7::new(1) / F7::new(3), F7::new(5));
+        assert_eq!(-F7::new(2), F7::new(5));
+        // Fermat: a^(p - 1) = 1
+        assert!((1..7).all(|a| F7::new(a).pow(6) == F7::one()));
+    }
+
+    #[test]
+    fn primality_is_exact() {
+        assert!(is_prime(2) && is_prime(65_537) && is_prime(1_000_000_007));
+        assert!(!is_prime(1) && !is_prime(561) && !is_prime(3_215_031_751));
+    }
+
+    #[test]
+    fn elimination_over_gf_p() {
+        let a = Mat2d::from([[1, 2], [3, 4]]).map(|&x| Gf::<5>::new(x));
+        let inverse = a.inverse().unwrap();
+        assert_eq!((&a * &inverse).unwrap(), Mat2d::identity(2, 2).unwrap());
+        // 1·4 - 2·3 = -2 = 3 mod 5
+        assert_eq!(a.determinant().unwrap(), Gf::new(3));
+
+        let singular = Mat2d::from([[1, 2], [3, 1]]).map(|&x| Gf::<5>::new(x));
+        assert_eq!(singular.rank(), 1);
+    }
+
+    fn gf2(rows: &[&str]) -> Gf2Mat {
+        Gf2Mat::from(
+            rows.iter()
+                .map(|row| row.chars().map(|c| c == '1').collect())
+                .collect::<Vec<Gf2Row>>(),
+        )
+    }
+
+    #[test]
+    fn packed_reduction_matches_gf2_scalars() {
+        let a = gf2(&["1101", "0111", "1010"]);
+        let reduced = Mat2d::from(&a).rref();
+        assert_eq!(Gf2Mat::from(&reduced), a.rref());
+        assert_eq!(a.rref(), gf2(&["1010", "0111", "0000"]));
+        assert_eq!(a.rank(), 2);
+
+        for v in a.null_space() {
+            assert!(a
+                .iter()
+                .all(|row| row.iter().zip(v.iter()).filter(|&(x, y)| x && y).count() % 2 == 0));
+        }
+        assert_eq!(a.null_space().len(), 2);
+    }
+
+    #[test]
+    fn packed_inverse_across_words() {
+        // a shift with wraparound, which needs more than one 64-bit word
+        let n = 70;
+        let mut a = Gf2Mat::identity(n);
+        for i in 0..n {
+            a.set(i, (i + 1) % n, true);
+        }
+        // I + S has an even number of ones per row and column, so is singular
+        assert!(matches!(a.inverse(), Err(LinAlgError::Singular { .. })));
+
+        a.set(0, 0, false);
+        let inverse = a.inverse().unwrap();
+        let product = Mat2d::from(&a) * Mat2d::from(&inverse);
+        assert_eq!(Gf2Mat::from(&product.unwrap()), Gf2Mat::identity(n));
+    }
+}
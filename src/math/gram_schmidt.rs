@@ -0,0 +1,137 @@
+use super::{qr::is_dependent, vec, RealField};
+
+#[derive(Debug, Clone)]
+pub enum GramSchmidtStep<T> {
+    // Subtract coeff * basis[onto] from vectors[index], leaving vector
+    Project {
+        index: usize,
+        onto: usize,
+        coeff: T,
+        vector: vec::Vec<T>,
+    },
+    // Divide the remainder of vectors[index] by its norm, leaving vector as
+    // the next basis vector
+    Normalize {
+        index: usize,
+        norm: T,
+        vector: vec::Vec<T>,
+    },
+    // Nothing is left of vectors[index] after the projections, so it lies in
+    // the span of the previous vectors and is skipped
+    Dependent {
+        index: usize,
+        remainder: T,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct GramSchmidt<T> {
+    // Orthonormal basis of the span of the input
+    pub basis: Vec<vec::Vec<T>>,
+    // Indices of the inputs that were skipped as linearly dependent
+    pub dependent: Vec<usize>,
+    pub steps: Vec<GramSchmidtStep<T>>,
+}
+
+// Classical Gram-Schmidt: each vector has its projections onto the basis
+// built so far subtracted, then is normalized
+pub fn gram_schmidt<T: RealField>(vectors: &[vec::Vec<T>]) -> GramSchmidt<T> {
+    let m = vectors.first().map_or(0, vec::Vec::len);
+    assert!(
+        vectors.iter().all(|v| v.len() == m),
+        "all vectors in gram_schmidt must have the same length"
+    );
+
+    let mut basis: Vec<vec::Vec<T>> = Vec::new();
+    let mut dependent = Vec::new();
+    let mut steps = Vec::new();
+
+    for (index, a) in vectors.iter().enumerate() {
+        let mut v = a.clone();
+        for (onto, q) in basis.iter().enumerate() {
+            // classical Gram-Schmidt projects the original vector
+            let coeff = q.dot(a);
+            v.sub_scaled(coeff, q);
+            steps.push(GramSchmidtStep::Project {
+                index,
+                onto,
+                coeff,
+                vector: v.clone(),
+            });
+        }
+
+        let remainder = v.norm_l2();
+        if is_dependent(remainder, a.norm_l2(), m) {
+            dependent.push(index);
+            steps.push(GramSchmidtStep::Dependent { index, remainder });
+            continue;
+        }
+        v /= remainder;
+        steps.push(GramSchmidtStep::Normalize {
+            index,
+            norm: remainder,
+            vector: v.clone(),
+        });
+        basis.push(v);
+    }
+
+    GramSchmidt {
+        basis,
+        dependent,
+        steps,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basis_is_orthonormal() {
+        let vectors = [
+            vec::Vec::from([1.0, 1.0, 0.0]),
+            vec::Vec::from([1.0, 0.0, 1.0]),
+            vec::Vec::from([0.0, 1.0, 1.0]),
+        ];
+        let gs = gram_schmidt(&vectors);
+        assert!(gs.dependent.is_empty());
+        for (i, q) in gs.basis.iter().enumerate() {
+            for (j, p) in gs.basis.iter().enumerate() {
+                let expected = if i == j { 1.0_f64 } else { 0.0 };
+                assert!((q.dot(p) - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn dependent_vectors_are_skipped() {
+        let vectors = [
+            vec::Vec::from([3.0, 4.0]),
+            vec::Vec::from([6.0, 8.0]),
+            vec::Vec::from([1.0, 0.0]),
+        ];
+        let gs = gram_schmidt(&vectors);
+        assert_eq!(gs.dependent, vec![1]);
+        assert_eq!(gs.basis.len(), 2);
+        assert_eq!(gs.basis[0], vec::Vec::from([0.6, 0.8]));
+    }
+
+    #[test]
+    fn steps_project_onto_earlier_basis_vectors() {
+        let vectors = [vec::Vec::from([3.0, 4.0]), vec::Vec::from([1.0, 0.0])];
+        let steps = gram_schmidt(&vectors).steps;
+        assert_eq!(steps.len(), 3);
+        assert!(matches!(
+            steps[0],
+            GramSchmidtStep::Normalize { index: 0, norm, .. } if norm == 5.0
+        ));
+        assert!(matches!(
+            steps[1],
+            GramSchmidtStep::Project { index: 1, onto: 0, coeff, .. } if (coeff - 0.6_f64).abs() < 1e-12
+        ));
+        assert!(matches!(
+            steps[2],
+            GramSchmidtStep::Normalize { index: 1, .. }
+        ));
+    }
+}
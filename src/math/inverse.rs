@@ -0,0 +1,180 @@
+use super::{
+    pivot::{clear_zeros, eliminate, first_nonzero, place_pivot, swap_pivot_row, Chooser},
+    tolerance::{Exact, ZeroTest},
+    Field, LinAlgError, Mat2d, OrderedField, Pivoting, RowOp,
+};
+
+#[derive(Debug)]
+pub struct InverseTrace<T> {
+    pub inverse: Mat2d<T>,
+    // Every operation applied to [A | I], paired with the matrix after it.
+    // Complete pivoting swaps columns of the left half as well as rows.
+    pub steps: Vec<(Mat2d<T>, RowOp<T>)>,
+}
+
+impl<T: Field> Mat2d<T> {
+    // A pivot that is only rounding residue by the zero test reports the
+    // matrix as singular
+    fn gauss_jordan_inverse<F>(
+        &self,
+        choose: Chooser<T>,
+        zero: &mut dyn ZeroTest<T>,
+        mut on_step: F,
+    ) -> Result<Self, LinAlgError>
+    where
+        F: FnMut(&Self, RowOp<T>),
+    {
+        let (m, n) = self.shape();
+        if m != n {
+            return Err(LinAlgError::NotSquare { shape: (m, n) });
+        }
+
+        let mut mat = self.augment_identity();
+        let mut permutation: Vec<usize> = (0..n).collect();
+
+        for i in 0..n {
+            // the pivot must land on the diagonal of the left half
+            let (row, col, col_swap) =
+                place_pivot(&mut mat, i, i, n, choose, zero, &mut permutation).ok_or(
+                    LinAlgError::Singular {
+                        col: permutation[i],
+                    },
+                )?;
+            if col != i {
+                return Err(LinAlgError::Singular {
+                    col: permutation[i],
+                });
+            }
+            if let Some(swap) = col_swap {
+                on_step(&mat, swap);
+            }
+            if let Some(swap) = swap_pivot_row(&mut mat, i, row) {
+                on_step(&mat, swap);
+            }
+
+            let pivot = mat[i][i].clone();
+            if !pivot.is_one() {
+                let scale = pivot.inv();
+                mat[i] *= scale.clone();
+                mat[i][i] = T::one();
+                clear_zeros(mat[i].as_mut_slice());
+                on_step(&mat, RowOp::Scale { scale, row: i });
+            }
+
+            for j in (0..n).filter(|&j| j != i) {
+                if !mat[j][i].is_zero() {
+                    let scale = eliminate(&mut mat, i, j, i, zero);
+                    on_step(
+                        &mat,
+                        RowOp::AddMultiple {
+                            scale,
+                            from: i,
+                            to: j,
+                        },
+                    );
+                }
+            }
+        }
+
+        // [AQ | I] reduces to [I | (AQ)⁻¹] and A⁻¹ = Q (AQ)⁻¹, so row k of
+        // the right half is row permutation[k] of A⁻¹
+        let right = mat.submatrix(.., n..);
+        let mut inverse = right.clone();
+        for (k, &row) in permutation.iter().enumerate() {
+            inverse[row] = right[k].clone();
+        }
+        Ok(inverse)
+    }
+
+    pub fn inverse(&self) -> Result<Self, LinAlgError> {
+        self.gauss_jordan_inverse(first_nonzero, &mut Exact, |_, _| {})
+    }
+
+    // With a tolerance the zero test is over the columns of [A | I], so a
+    // numerically singular matrix is an error rather than an inverse of
+    // enormous entries
+    pub fn inverse_with(&self, pivoting: impl Into<Pivoting<T>>) -> Result<Self, LinAlgError>
+    where
+        T: OrderedField,
+    {
+        let pivoting = pivoting.into();
+        let mut zero = pivoting.zero_test(&self.augment_identity());
+        self.gauss_jordan_inverse(pivoting.strategy.chooser(), &mut zero, |_, _| {})
+    }
+
+    pub fn inverse_verbose(&self) -> Result<InverseTrace<T>, LinAlgError> {
+        let mut steps = Vec::new();
+        let inverse = self.gauss_jordan_inverse(first_nonzero, &mut Exact, |mat, step| {
+            steps.push((mat.clone(), step))
+        })?;
+        Ok(InverseTrace { inverse, steps })
+    }
+
+    pub fn inverse_verbose_with(
+        &self,
+        pivoting: impl Into<Pivoting<T>>,
+    ) -> Result<InverseTrace<T>, LinAlgError>
+    where
+        T: OrderedField,
+    {
+        let pivoting = pivoting.into();
+        let mut zero = pivoting.zero_test(&self.augment_identity());
+        let mut steps = Vec::new();
+        let inverse =
+            self.gauss_jordan_inverse(pivoting.strategy.chooser(), &mut zero, |mat, step| {
+                steps.push((mat.clone(), step))
+            })?;
+        Ok(InverseTrace { inverse, steps })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{rational::rational, PivotStrategy, Rational};
+
+    fn mat<const N: usize>(rows: [[i64; N]; N]) -> Mat2d<Rational> {
+        Mat2d::from(rows).map(|&x| rational(x, 1))
+    }
+
+    #[test]
+    fn inverse_times_matrix_is_identity() {
+        let a = mat([[0, 1, 2], [1, 0, 3], [4, -3, 8]]);
+        let identity = Mat2d::identity(3, 3).unwrap();
+        for strategy in [
+            PivotStrategy::FirstNonZero,
+            PivotStrategy::Partial,
+            PivotStrategy::Complete,
+            PivotStrategy::Simplest,
+        ] {
+            let inverse = a.inverse_with(strategy).unwrap();
+            assert_eq!((&a * &inverse).unwrap(), identity);
+        }
+    }
+
+    #[test]
+    fn trace_replays_on_the_augmented_matrix() {
+        let a = mat([[0, 2], [1, 1]]);
+        let trace = a.inverse_verbose().unwrap();
+        assert_eq!(trace.steps[0].1, RowOp::Swap { from: 0, to: 1 });
+
+        let mut augmented = a.augment_identity();
+        for (mat, op) in &trace.steps {
+            op.apply(&mut augmented);
+            assert_eq!(&augmented, mat);
+        }
+        assert_eq!(augmented.submatrix(.., 2..), trace.inverse);
+    }
+
+    #[test]
+    fn singular_and_non_square_matrices() {
+        assert!(matches!(
+            mat([[1, 2], [2, 4]]).inverse(),
+            Err(LinAlgError::Singular { col: 1 })
+        ));
+        assert!(matches!(
+            Mat2d::<Rational>::zeros(2, 3).inverse(),
+            Err(LinAlgError::NotSquare { shape: (2, 3) })
+        ));
+    }
+}
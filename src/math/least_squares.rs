@@ -0,0 +1,137 @@
+use super::{qr::is_dependent, vec, LinAlgError, Mat2d, QrMethod, RealField};
+
+#[derive(Debug, Clone)]
+pub struct LeastSquares<T> {
+    // x minimizing |Ax - b|
+    pub solution: vec::Vec<T>,
+    // b - Ax
+    pub residual: vec::Vec<T>,
+    pub residual_norm: T,
+    // 1 - SS_res / SS_tot, the fraction of the variance of b explained by Ax
+    pub r_squared: T,
+}
+
+// The normal equations AᵀA x = Aᵀb, kept for showing the derivation
+#[derive(Debug, Clone)]
+pub struct NormalEquations<T> {
+    pub ata: Mat2d<T>,
+    pub atb: vec::Vec<T>,
+    pub fit: LeastSquares<T>,
+}
+
+impl<T: RealField> Mat2d<T> {
+    // Solves R x = Qᵀb with a Householder QR, which avoids squaring the
+    // condition number the way the normal equations do
+    pub fn least_squares(&self, b: &vec::Vec<T>) -> Result<LeastSquares<T>, LinAlgError> {
+        let (m, n) = self.shape();
+        if b.len() != m {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: (m, 1),
+                found: (b.len(), 1),
+            });
+        }
+        if m < n {
+            return Err(LinAlgError::LinearlyDependent { col: m });
+        }
+
+        let qr = self.qr(QrMethod::Householder)?;
+        let qtb = (b * &qr.q)?;
+
+        let mut x = vec::Vec::zeros(n);
+        for i in (0..n).rev() {
+            if is_dependent(qr.r[i][i].abs(), self.col(i).norm_l2(), m) {
+                return Err(LinAlgError::LinearlyDependent { col: i });
+            }
+            let s = (i + 1..n).fold(qtb[i], |acc, j| acc - qr.r[i][j] * x[j]);
+            x[i] = s / qr.r[i][i];
+        }
+
+        self.fit(b, x)
+    }
+
+    pub fn least_squares_normal(&self, b: &vec::Vec<T>) -> Result<NormalEquations<T>, LinAlgError> {
+        let at = self.transpose();
+        let ata = (&at * self)?;
+        let atb = (&at * b)?;
+        let x = ata.lu()?.solve(&atb)?;
+
+        Ok(NormalEquations {
+            fit: self.fit(b, x)?,
+            ata,
+            atb,
+        })
+    }
+
+    fn fit(&self, b: &vec::Vec<T>, solution: vec::Vec<T>) -> Result<LeastSquares<T>, LinAlgError> {
+        let residual = b - (self * &solution)?;
+        let ss_res = residual.dot(&residual);
+
+        let mean = b.iter().fold(T::zero(), |acc, &x| acc + x) / T::from_usize(b.len().max(1));
+        let ss_tot = b
+            .iter()
+            .fold(T::zero(), |acc, &x| acc + (x - mean) * (x - mean));
+        // a constant b is explained perfectly by any fit through its mean
+        let r_squared = if ss_tot == T::zero() {
+            T::one()
+        } else {
+            T::one() - ss_res / ss_tot
+        };
+
+        Ok(LeastSquares {
+            solution,
+            residual_norm: ss_res.sqrt(),
+            residual,
+            r_squared,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(x: f64, y: f64) -> bool {
+        (x - y).abs() < 1e-12
+    }
+
+    #[test]
+    fn line_through_three_points() {
+        let a = Mat2d::from([[1.0, 0.0], [1.0, 1.0], [1.0, 2.0]]);
+        let b = vec::Vec::from([0.0, 1.0, 1.0]);
+        let qr = a.least_squares(&b).unwrap();
+        let normal = a.least_squares_normal(&b).unwrap();
+        for fit in [&qr, &normal.fit] {
+            assert!(close(fit.solution[0], 1.0 / 6.0));
+            assert!(close(fit.solution[1], 0.5));
+            assert!(close(fit.r_squared, 0.75));
+            assert!(close(fit.residual_norm, (1.0_f64 / 6.0).sqrt()));
+            // the residual is orthogonal to the columns of A
+            assert!(close(fit.residual.dot(&a.col(1)), 0.0));
+        }
+        assert_eq!(normal.ata, Mat2d::from([[3.0, 3.0], [3.0, 5.0]]));
+        assert_eq!(normal.atb, vec::Vec::from([2.0, 3.0]));
+    }
+
+    #[test]
+    fn exact_fit_has_no_residual() {
+        let a = Mat2d::from([[1.0, 0.0], [1.0, 1.0], [1.0, 2.0]]);
+        let fit = a.least_squares(&vec::Vec::from([1.0, 3.0, 5.0])).unwrap();
+        assert!(close(fit.solution[0], 1.0) && close(fit.solution[1], 2.0));
+        assert!(close(fit.residual_norm, 0.0));
+        assert!(close(fit.r_squared, 1.0));
+    }
+
+    #[test]
+    fn rejects_dependent_columns_and_wrong_lengths() {
+        let a = Mat2d::from([[1.0, 2.0], [2.0, 4.0], [3.0, 6.0]]);
+        let b = vec::Vec::from([1.0, 2.0, 3.0]);
+        assert!(matches!(
+            a.least_squares(&b),
+            Err(LinAlgError::LinearlyDependent { col: 1 })
+        ));
+        assert!(matches!(
+            a.least_squares(&vec::Vec::from([1.0])),
+            Err(LinAlgError::DimensionMismatch { .. })
+        ));
+    }
+}
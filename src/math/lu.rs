@@ -0,0 +1,230 @@
+use super::{tolerance::ZeroTest, vec, LinAlgError, Mat2d, OrderedField, PivotStrategy, Pivoting};
+
+#[derive(Debug, Clone)]
+pub struct LuDecomposition<T> {
+    pub p: Mat2d<T>,
+    pub l: Mat2d<T>,
+    pub u: Mat2d<T>,
+    // Row i of PA is row permutation[i] of A
+    pub permutation: Vec<usize>,
+    // +1 for an even permutation, -1 for an odd one
+    pub parity: i8,
+}
+
+impl<T: OrderedField> Mat2d<T> {
+    // Factors PA = LU with partial pivoting
+    pub fn lu(&self) -> Result<LuDecomposition<T>, LinAlgError> {
+        self.lu_with(PivotStrategy::Partial)
+    }
+
+    // Complete pivoting would need a column permutation too (PAQ = LU), so it
+    // searches the pivot column only, which is partial pivoting. With a
+    // tolerance, a pivot column that is rounding residue counts as zero.
+    pub fn lu_with(
+        &self,
+        pivoting: impl Into<Pivoting<T>>,
+    ) -> Result<LuDecomposition<T>, LinAlgError> {
+        let (m, n) = self.shape();
+        if m != n {
+            return Err(LinAlgError::NotSquare { shape: (m, n) });
+        }
+
+        let pivoting = pivoting.into().in_column_order();
+        let choose = pivoting.strategy.chooser();
+        let mut zero = pivoting.zero_test(self);
+        let mut a = self.clone();
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut parity = 1;
+
+        for k in 0..n {
+            // a zero pivot column needs no elimination
+            if (k..n).all(|i| zero.is_zero(&a[i][k], k)) {
+                for i in k..n {
+                    a[i][k] = T::zero();
+                }
+                continue;
+            }
+            // the column is not zero, so the pivot is in it
+            let (idx, _) = choose(&a, k, k..n, &zero).unwrap();
+            if idx != k {
+                a.swap_row(k, idx);
+                permutation.swap(k, idx);
+                parity = -parity;
+            }
+
+            let pivot = a[k][k].clone();
+            for i in k + 1..n {
+                zero.record(a[i].as_slice());
+                let scale = a[i][k].clone() / pivot.clone();
+                for j in k + 1..n {
+                    a[i][j] = a[i][j].clone() - scale.clone() * a[k][j].clone();
+                }
+                // the multiplier is kept below the diagonal for L
+                a[i][k] = scale;
+            }
+        }
+
+        let mut p = Self::zeros(n, n);
+        let mut l = Self::identity(n, n).unwrap();
+        let mut u = Self::zeros(n, n);
+        for i in 0..n {
+            p[i][permutation[i]] = T::one();
+            for j in 0..n {
+                if j < i {
+                    l[i][j] = a[i][j].clone();
+                } else {
+                    u[i][j] = a[i][j].clone();
+                }
+            }
+        }
+
+        Ok(LuDecomposition {
+            p,
+            l,
+            u,
+            permutation,
+            parity,
+        })
+    }
+}
+
+impl<T: OrderedField> LuDecomposition<T> {
+    pub fn solve(&self, b: &vec::Vec<T>) -> Result<vec::Vec<T>, LinAlgError> {
+        let (n, _) = self.u.shape();
+        if b.len() != n {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: (n, 1),
+                found: (b.len(), 1),
+            });
+        }
+
+        // forward substitution on Ly = Pb
+        let mut y: vec::Vec<T> = self.permutation.iter().map(|&i| b[i].clone()).collect();
+        for i in 0..n {
+            for j in 0..i {
+                y[i] = y[i].clone() - self.l[i][j].clone() * y[j].clone();
+            }
+        }
+
+        // backward substitution on Ux = y
+        let mut x = y;
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                x[i] = x[i].clone() - self.u[i][j].clone() * x[j].clone();
+            }
+            if self.u[i][i].is_zero() {
+                return Err(LinAlgError::Singular { col: i });
+            }
+            x[i] = x[i].clone() / self.u[i][i].clone();
+        }
+
+        Ok(x)
+    }
+
+    pub fn solve_many(&self, b: &Mat2d<T>) -> Result<Mat2d<T>, LinAlgError> {
+        let (n, _) = self.u.shape();
+        let (m, k) = b.shape();
+        if m != n {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: (n, k),
+                found: (m, k),
+            });
+        }
+
+        let mut x = Mat2d::zeros(n, k);
+        for j in 0..k {
+            let col = self.solve(&b.col(j))?;
+            for i in 0..n {
+                x[i][j] = col[i].clone();
+            }
+        }
+
+        Ok(x)
+    }
+
+    pub fn determinant(&self) -> T {
+        let (n, _) = self.u.shape();
+        let sign = if self.parity < 0 { -T::one() } else { T::one() };
+        (0..n).fold(sign, |acc, i| acc * self.u[i][i].clone())
+    }
+
+    pub fn inverse(&self) -> Result<Mat2d<T>, LinAlgError> {
+        let (n, _) = self.u.shape();
+        self.solve_many(&Mat2d::identity(n, n).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{rational::rational, Rational, Tolerance};
+    use super::*;
+
+    fn mat(rows: &[&[i64]]) -> Mat2d<Rational> {
+        Mat2d::from(
+            rows.iter()
+                .map(|row| row.iter().map(|&x| rational(x, 1)).collect())
+                .collect::<Vec<vec::Vec<Rational>>>(),
+        )
+    }
+
+    #[test]
+    fn factors_reconstruct_the_matrix() {
+        let a = mat(&[&[1, 2, 3], &[4, 5, 6], &[7, 8, 10]]);
+        for strategy in [
+            PivotStrategy::FirstNonZero,
+            PivotStrategy::Partial,
+            PivotStrategy::Complete,
+        ] {
+            let lu = a.lu_with(strategy).unwrap();
+            assert_eq!((&lu.p * &a).unwrap(), (&lu.l * &lu.u).unwrap());
+            assert_eq!(lu.determinant(), a.determinant().unwrap());
+        }
+        assert_eq!(a.lu().unwrap().permutation, vec![2, 0, 1]);
+        assert_eq!(a.lu_with(PivotStrategy::FirstNonZero).unwrap().parity, 1);
+    }
+
+    #[test]
+    fn solves_and_inverts() {
+        let a = mat(&[&[0, 2], &[3, 1]]);
+        let lu = a.lu().unwrap();
+        let x = lu
+            .solve(&vec::Vec::from([rational(4, 1), rational(5, 1)]))
+            .unwrap();
+        assert_eq!(x.as_slice(), &[rational(1, 1), rational(2, 1)]);
+        assert_eq!(lu.inverse().unwrap(), a.inverse().unwrap());
+    }
+
+    #[test]
+    fn tolerance_finds_the_singular_matrix() {
+        let a = Mat2d::from(vec![
+            vec::Vec::from([0.1, 0.2, 0.3]),
+            vec::Vec::from([0.4, 0.5, 0.6]),
+            vec::Vec::from([0.7, 0.8, 0.9]),
+        ]);
+        let lu = a
+            .lu_with(Pivoting::new(PivotStrategy::Partial, Tolerance::default()))
+            .unwrap();
+        assert_eq!(lu.determinant(), 0.0);
+        assert!(matches!(
+            lu.solve(&vec::Vec::from([1.0, 1.0, 1.0])),
+            Err(LinAlgError::Singular { col: 2 })
+        ));
+    }
+
+    #[test]
+    fn singular_and_non_square_matrices() {
+        // the zero first column is skipped rather than divided by
+        let a = mat(&[&[0, 1], &[0, 2]]);
+        let lu = a.lu().unwrap();
+        assert_eq!((&lu.p * &a).unwrap(), (&lu.l * &lu.u).unwrap());
+        assert_eq!(lu.determinant(), rational(0, 1));
+        assert!(matches!(
+            lu.solve(&vec::Vec::from([rational(1, 1), rational(2, 1)])),
+            Err(LinAlgError::Singular { .. })
+        ));
+        assert!(matches!(
+            mat(&[&[1, 2]]).lu(),
+            Err(LinAlgError::NotSquare { shape: (1, 2) })
+        ));
+    }
+}
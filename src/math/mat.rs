@@ -1,30 +1,82 @@
-use super::vec;
+use super::{
+    algebra::{EuclideanRing, Field},
+    pivot::{clear_zeros, eliminate, first_nonzero, place_pivot, swap_pivot_row, Chooser},
+    tolerance::{Exact, ZeroTest},
+    vec, LinAlgError, OrderedField, Pivoting, RowOp,
+};
 use std::{
     fmt::Debug,
-    ops::{Div, Index, IndexMut, Mul, Sub},
+    ops::{
+        Add, AddAssign, Bound, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Range,
+        RangeBounds, Sub, SubAssign,
+    },
     slice,
 };
 
-use num_traits::{identities, Float};
+use num_traits::identities;
 
 #[derive(Clone, PartialEq)]
 pub struct Mat2d<T> {
     mat: Vec<vec::Vec<T>>,
+    // the length of every row, kept so that a matrix without rows still has
+    // a column count
+    cols: usize,
 }
 
 #[derive(Debug)]
-pub enum Step<T> {
-    Swap { from: usize, to: usize },
-    Sub { scale: T, from: usize, to: usize },
+pub struct DeterminantTrace<T> {
+    pub determinant: T,
+    // Each step carries the factor f such that det(A) = f * det(step matrix).
+    // Column swaps from complete pivoting are steps too.
+    pub steps: Vec<(Mat2d<T>, RowOp<T>, T)>,
+    // The factor after the last step: ±1 by the parity of all the swaps
+    pub sign: T,
+    // The diagonal of the echelon form. The determinant is sign times its
+    // product, or zero when elimination ran out of pivots.
+    pub diagonal: Vec<T>,
+    // Column j of the echelon form is column col_permutation[j] of A. Only
+    // complete pivoting moves columns.
+    pub col_permutation: Vec<usize>,
+}
+
+// The stages of Gauss-Jordan elimination: zeros below the pivots, pivots
+// scaled to one, then zeros above the pivots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Forward,
+    Normalize,
+    Backward,
 }
 
-impl<T: Copy> Mat2d<T> {
+#[derive(Debug, Clone)]
+pub struct RrefTrace<T> {
+    pub rref: Mat2d<T>,
+    pub steps: Vec<(Mat2d<T>, RowOp<T>, Phase)>,
+    // Column j of the reduced matrix is column col_permutation[j] of A. Only
+    // complete pivoting moves columns.
+    pub col_permutation: Vec<usize>,
+    // Column of the pivot in each non-zero row
+    pub pivots: Vec<usize>,
+}
+
+// Echelon form of AQ, where Q is the column permutation from complete
+// pivoting (the identity for every other strategy)
+#[derive(Debug, Clone)]
+pub struct Echelon<T> {
+    pub echelon: Mat2d<T>,
+    pub steps: Vec<(Mat2d<T>, RowOp<T>)>,
+    pub col_permutation: Vec<usize>,
+    pub pivots: Vec<usize>,
+}
+
+impl<T: Clone> Mat2d<T> {
     pub fn zeros(m: usize, n: usize) -> Self
     where
         T: identities::Zero,
     {
         Self {
             mat: vec![vec::Vec::zeros(n); m],
+            cols: n,
         }
     }
 
@@ -34,6 +86,7 @@ impl<T: Copy> Mat2d<T> {
     {
         Self {
             mat: vec![vec::Vec::ones(n); m],
+            cols: n,
         }
     }
 
@@ -58,118 +111,430 @@ impl<T: Copy> Mat2d<T> {
         self.mat.swap(from, to);
     }
 
-    pub fn row_reduced(&self) -> Self
+    pub fn swap_col(&mut self, from: usize, to: usize) {
+        for row in self.iter_mut() {
+            row.as_mut_slice().swap(from, to);
+        }
+    }
+
+    // row[to] -= scale * row[from]
+    pub fn sub_scaled_row(&mut self, from: usize, to: usize, scale: T)
     where
-        T: Float,
+        T: Sub<Output = T> + Mul<Output = T>,
+    {
+        assert!(from != to, "cannot subtract a row from itself");
+        let (src, dst) = if from < to {
+            let (a, b) = self.mat.split_at_mut(to);
+            (&a[from], &mut b[0])
+        } else {
+            let (a, b) = self.mat.split_at_mut(from);
+            (&b[0], &mut a[to])
+        };
+        dst.sub_scaled(scale, src);
+    }
+
+    pub fn hadamard(&self, rhs: &Self) -> Self
+    where
+        T: Mul<Output = T>,
+    {
+        assert!(
+            self.shape() == rhs.shape(),
+            "two matrices in a hadamard product must have the same shape"
+        );
+
+        Self {
+            mat: self
+                .mat
+                .iter()
+                .zip(rhs.iter())
+                .map(|(a, b)| a.hadamard(b))
+                .collect(),
+            cols: self.cols,
+        }
+    }
+
+    pub fn hadamard_div(&self, rhs: &Self) -> Self
+    where
+        T: Div<Output = T>,
+    {
+        assert!(
+            self.shape() == rhs.shape(),
+            "two matrices in a hadamard division must have the same shape"
+        );
+
+        Self {
+            mat: self
+                .mat
+                .iter()
+                .zip(rhs.iter())
+                .map(|(a, b)| a.hadamard_div(b))
+                .collect(),
+            cols: self.cols,
+        }
+    }
+
+    // Reduces to echelon form with the given pivot choice, taking pivots from
+    // the first limit columns only. Returns it with the column permutation any
+    // complete pivoting applied and the column of each pivot in row order.
+    fn forward_eliminate<F>(
+        &self,
+        choose: Chooser<T>,
+        zero: &mut dyn ZeroTest<T>,
+        limit: usize,
+        mut on_step: F,
+    ) -> (Self, Vec<usize>, Vec<usize>)
+    where
+        T: Field,
+        F: FnMut(&Self, RowOp<T>),
     {
         let mut mat = self.clone();
         let (m, n) = self.shape();
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut pivots = Vec::new();
 
-        for i in 0..m.min(n) {
-            // find a non-zero pivot
-            if mat[i][i] == T::zero() {
-                // There exists a non-zero pivot in the ith column
-                if let Some(idx) = mat.iter().skip(i).position(|x| !T::zero().eq(&x[i])) {
-                    mat.swap_row(i, idx);
-                } else {
-                    continue;
-                }
+        // i is the row the next pivot goes to, so zero columns are skipped
+        // instead of leaving zero rows above non-zero ones
+        let mut col = 0;
+        for i in 0..m {
+            let Some((row, c, col_swap)) =
+                place_pivot(&mut mat, i, col, limit, choose, zero, &mut permutation)
+            else {
+                break;
+            };
+            col = c;
+            pivots.push(col);
+            if let Some(swap) = col_swap {
+                on_step(&mat, swap);
+            }
+            if let Some(swap) = swap_pivot_row(&mut mat, i, row) {
+                on_step(&mat, swap);
             }
+
             for j in i + 1..m {
-                if mat[j][i] != T::zero() {
-                    let scale = mat[j][i] / mat[i][i];
-                    mat[j] = mat[j].sub(&mat[i].mul(scale));
+                if !mat[j][col].is_zero() {
+                    let scale = eliminate(&mut mat, i, j, col, zero);
+                    on_step(
+                        &mat,
+                        RowOp::AddMultiple {
+                            scale,
+                            from: i,
+                            to: j,
+                        },
+                    );
                 }
             }
+            col += 1;
         }
 
-        mat
+        (mat, permutation, pivots)
+    }
+
+    pub fn row_reduced(&self) -> Self
+    where
+        T: Field,
+    {
+        let (_, n) = self.shape();
+        self.forward_eliminate(first_nonzero, &mut Exact, n, |_, _| {})
+            .0
     }
 
-    pub fn row_reduced_verbose(&self) -> Vec<(Self, Step<T>)>
+    pub fn row_reduced_verbose(&self) -> Vec<(Self, RowOp<T>)>
     where
-        T: Float,
+        T: Field,
     {
+        let (_, n) = self.shape();
         let mut steps = Vec::new();
+        self.forward_eliminate(first_nonzero, &mut Exact, n, |mat, step| {
+            steps.push((mat.clone(), step))
+        });
+        steps
+    }
+
+    // Echelon form using only ring operations, so an integer matrix stays
+    // integer. Below a pivot p, a row with entry a becomes
+    // (p / g) R_j - (a / g) R_i for g = gcd(p, a), and is then divided by the
+    // gcd of its entries to keep them small.
+    pub fn row_reduced_fraction_free(&self) -> Self
+    where
+        T: EuclideanRing,
+    {
         let mut mat = self.clone();
         let (m, n) = self.shape();
+        let mut i = 0;
+        for col in 0..n {
+            if i == m {
+                break;
+            }
+            let Some(row) = (i..m).find(|&row| !mat[row][col].is_zero()) else {
+                continue;
+            };
+            mat.swap_row(i, row);
 
-        for i in 0..m.min(n) {
-            // find a non-zero pivot
-            if mat[i][i] == T::zero() {
-                // There exists a non-zero pivot in the ith column
-                if let Some(idx) = mat.iter().skip(i).position(|x| !T::zero().eq(&x[i])) {
-                    mat.swap_row(i, idx);
-                    steps.push((mat.clone(), Step::Swap { from: i, to: idx }));
-                } else {
+            for j in i + 1..m {
+                let a = mat[j][col].clone();
+                if a.is_zero() {
                     continue;
                 }
-            }
-            for j in i + 1..m {
-                if mat[j][i] != T::zero() {
-                    let scale = mat[j][i] / mat[i][i];
-                    mat[j] = mat[j].sub(&mat[i].mul(scale));
-                    steps.push((
-                        mat.clone(),
-                        Step::Sub {
-                            scale,
-                            from: i,
-                            to: j,
-                        },
-                    ));
+                let p = mat[i][col].clone();
+                let g = p.gcd(&a);
+                let (p, a) = (p.div_rem(&g).0, a.div_rem(&g).0);
+                // rows i and j are zero left of col
+                for k in col..n {
+                    let x = p.clone() * mat[j][k].clone() - a.clone() * mat[i][k].clone();
+                    mat[j][k] = x;
+                }
+                let content = mat[j].iter().fold(T::zero(), |acc, x| acc.gcd(x));
+                if !content.is_zero() {
+                    for x in mat[j].iter_mut() {
+                        *x = x.div_rem(&content).0;
+                    }
                 }
             }
+            i += 1;
         }
+        mat
+    }
 
-        steps
+    pub fn row_reduced_with(&self, pivoting: impl Into<Pivoting<T>>) -> Echelon<T>
+    where
+        T: OrderedField,
+    {
+        let pivoting = pivoting.into();
+        let (_, n) = self.shape();
+        let mut steps = Vec::new();
+        let (echelon, col_permutation, pivots) = self.forward_eliminate(
+            pivoting.strategy.chooser(),
+            &mut pivoting.zero_test(self),
+            n,
+            |mat, step| steps.push((mat.clone(), step)),
+        );
+        Echelon {
+            echelon,
+            steps,
+            col_permutation,
+            pivots,
+        }
     }
 
-    pub fn rref(&self) -> Self
+    // The determinant from the echelon form. The trace comes back without
+    // steps, which go to on_step with the factor f such that
+    // det(A) = f * det(step matrix).
+    fn eliminate_determinant<F>(
+        &self,
+        choose: Chooser<T>,
+        zero: &mut dyn ZeroTest<T>,
+        mut on_step: F,
+    ) -> Result<DeterminantTrace<T>, LinAlgError>
     where
-        T: Float,
+        T: Field,
+        F: FnMut(&Self, RowOp<T>, &T),
     {
-        let mut mat = self.row_reduced();
         let (m, n) = self.shape();
+        if m != n {
+            return Err(LinAlgError::NotSquare { shape: (m, n) });
+        }
 
-        for i in (0..n).rev() {
-            if let Some(idx) = mat
-                .iter()
-                .map(|x| x[i])
-                .rev()
-                .position(|x| !x.eq(&T::zero()))
-            {
-                let idx = m - idx - 1;
-                if mat[idx][i] != T::zero() {
-                    mat[idx] = mat[idx].div(mat[idx][i])
-                }
+        let mut sign = T::one();
+        let (mat, col_permutation, pivots) =
+            self.forward_eliminate(choose, zero, n, |mat, step| {
+                sign = sign.clone() / step.determinant_factor();
+                on_step(mat, step, &sign);
+            });
+        let diagonal: Vec<T> = (0..n).map(|i| mat[i][i].clone()).collect();
+        let determinant = if pivots.len() < n {
+            T::zero()
+        } else {
+            diagonal.iter().fold(sign.clone(), |acc, x| acc * x.clone())
+        };
 
-                for j in (0..idx).rev() {
-                    if mat[j][i] != T::zero() {
-                        let scale = mat[j][i] / mat[idx][i];
-                        mat[j] = mat[j].sub(&mat[idx].mul(scale));
-                    }
+        Ok(DeterminantTrace {
+            determinant,
+            steps: Vec::new(),
+            sign,
+            diagonal,
+            col_permutation,
+        })
+    }
+
+    pub fn determinant(&self) -> Result<T, LinAlgError>
+    where
+        T: Field,
+    {
+        self.eliminate_determinant(first_nonzero, &mut Exact, |_, _, _| {})
+            .map(|trace| trace.determinant)
+    }
+
+    pub fn determinant_with(&self, pivoting: impl Into<Pivoting<T>>) -> Result<T, LinAlgError>
+    where
+        T: OrderedField,
+    {
+        let pivoting = pivoting.into();
+        let mut zero = pivoting.zero_test(self);
+        self.eliminate_determinant(pivoting.strategy.chooser(), &mut zero, |_, _, _| {})
+            .map(|trace| trace.determinant)
+    }
+
+    pub fn determinant_verbose(&self) -> Result<DeterminantTrace<T>, LinAlgError>
+    where
+        T: Field,
+    {
+        self.determinant_trace(first_nonzero, &mut Exact)
+    }
+
+    pub fn determinant_verbose_with(
+        &self,
+        pivoting: impl Into<Pivoting<T>>,
+    ) -> Result<DeterminantTrace<T>, LinAlgError>
+    where
+        T: OrderedField,
+    {
+        let pivoting = pivoting.into();
+        self.determinant_trace(pivoting.strategy.chooser(), &mut pivoting.zero_test(self))
+    }
+
+    fn determinant_trace(
+        &self,
+        choose: Chooser<T>,
+        zero: &mut dyn ZeroTest<T>,
+    ) -> Result<DeterminantTrace<T>, LinAlgError>
+    where
+        T: Field,
+    {
+        let mut steps = Vec::new();
+        let trace = self.eliminate_determinant(choose, zero, |mat, step, factor| {
+            steps.push((mat.clone(), step, factor.clone()))
+        })?;
+        Ok(DeterminantTrace { steps, ..trace })
+    }
+
+    // Gauss-Jordan elimination, taking pivots from the first limit columns
+    // only. The trace comes back without steps, which go to on_step.
+    pub(super) fn gauss_jordan<F>(
+        &self,
+        choose: Chooser<T>,
+        zero: &mut dyn ZeroTest<T>,
+        limit: usize,
+        mut on_step: F,
+    ) -> RrefTrace<T>
+    where
+        T: Field,
+        F: FnMut(&Self, RowOp<T>, Phase),
+    {
+        let (mut mat, col_permutation, pivots) =
+            self.forward_eliminate(choose, zero, limit, |mat, op| {
+                on_step(mat, op, Phase::Forward)
+            });
+
+        for (i, &col) in pivots.iter().enumerate() {
+            let pivot = mat[i][col].clone();
+            if !pivot.is_one() {
+                let op = RowOp::Scale {
+                    scale: pivot.inv(),
+                    row: i,
+                };
+                op.apply(&mut mat);
+                mat[i][col] = T::one();
+                clear_zeros(mat[i].as_mut_slice());
+                on_step(&mat, op, Phase::Normalize);
+            }
+        }
+
+        for (i, &col) in pivots.iter().enumerate().rev() {
+            for j in (0..i).rev() {
+                if !mat[j][col].is_zero() {
+                    let scale = eliminate(&mut mat, i, j, col, zero);
+                    let op = RowOp::AddMultiple {
+                        scale,
+                        from: i,
+                        to: j,
+                    };
+                    on_step(&mat, op, Phase::Backward);
                 }
             }
         }
 
-        mat
+        RrefTrace {
+            rref: mat,
+            steps: Vec::new(),
+            col_permutation,
+            pivots,
+        }
+    }
+
+    pub fn rref(&self) -> Self
+    where
+        T: Field,
+    {
+        let (_, n) = self.shape();
+        self.gauss_jordan(first_nonzero, &mut Exact, n, |_, _, _| {})
+            .rref
+    }
+
+    pub fn rref_verbose(&self) -> RrefTrace<T>
+    where
+        T: Field,
+    {
+        self.rref_trace(first_nonzero, &mut Exact)
+    }
+
+    // With complete pivoting this is the RREF of AQ, where column j of AQ is
+    // column col_permutation[j] of A. With a tolerance, computed entries that
+    // are rounding residue come out as exact zeros; the input entries are
+    // never changed.
+    pub fn rref_with(&self, pivoting: impl Into<Pivoting<T>>) -> RrefTrace<T>
+    where
+        T: OrderedField,
+    {
+        let pivoting = pivoting.into();
+        self.rref_trace(pivoting.strategy.chooser(), &mut pivoting.zero_test(self))
+    }
+
+    fn rref_trace(&self, choose: Chooser<T>, zero: &mut dyn ZeroTest<T>) -> RrefTrace<T>
+    where
+        T: Field,
+    {
+        let (_, n) = self.shape();
+        let mut steps = Vec::new();
+        let trace = self.gauss_jordan(choose, zero, n, |mat, op, phase| {
+            steps.push((mat.clone(), op, phase))
+        });
+        RrefTrace { steps, ..trace }
     }
 
     pub fn rank(&self) -> usize
     where
-        T: Float,
+        T: Field,
+    {
+        self.row_reduced()
+            .iter()
+            .filter(|v| v.iter().any(|x| !x.is_zero()))
+            .count()
+    }
+
+    // The number of pivots, which with a tolerance counts only those above it
+    pub fn rank_with(&self, pivoting: impl Into<Pivoting<T>>) -> usize
+    where
+        T: OrderedField,
+    {
+        self.row_reduced_with(pivoting).pivots.len()
+    }
+
+    pub fn map<U, F>(&self, mut f: F) -> Mat2d<U>
+    where
+        F: FnMut(&T) -> U,
     {
-        let rref = self.rref();
-        rref.iter()
-            .map(|v| v.iter().any(|x| !x.is_zero()) as usize)
-            .reduce(|a, b| a + b)
-            .unwrap()
+        Mat2d {
+            mat: self
+                .mat
+                .iter()
+                .map(|v| v.iter().map(&mut f).collect())
+                .collect(),
+            cols: self.cols,
+        }
     }
 
     pub fn shape(&self) -> (usize, usize) {
-        let m = self.mat.len();
-        let n = self.mat.get(0).map(|x| x.len()).unwrap_or(0);
-        (m, n)
+        (self.mat.len(), self.cols)
     }
 
     pub fn iter(&self) -> slice::Iter<'_, vec::Vec<T>> {
@@ -180,8 +545,174 @@ impl<T: Copy> Mat2d<T> {
         self.mat.iter_mut()
     }
 
+    pub fn row(&self, i: usize) -> vec::Vec<T> {
+        self.mat[i].clone()
+    }
+
+    pub fn col(&self, j: usize) -> vec::Vec<T> {
+        self.mat.iter().map(|v| v[j].clone()).collect()
+    }
+
+    // k columns of length m make an m x k matrix, even when m is 0
+    pub fn from_cols(cols: Vec<vec::Vec<T>>) -> Self {
+        let m = cols.first().map_or(0, vec::Vec::len);
+        assert!(
+            cols.iter().all(|v| v.len() == m),
+            "all columns of a matrix must have the same length"
+        );
+        Self {
+            mat: (0..m)
+                .map(|i| cols.iter().map(|v| v[i].clone()).collect())
+                .collect(),
+            cols: cols.len(),
+        }
+    }
+
     pub fn transpose(&self) -> Self {
-        todo!()
+        let (m, n) = self.shape();
+        Self {
+            mat: (0..n).map(|j| self.col(j)).collect(),
+            cols: m,
+        }
+    }
+
+    pub fn submatrix<R, C>(&self, rows: R, cols: C) -> Self
+    where
+        R: RangeBounds<usize>,
+        C: RangeBounds<usize>,
+    {
+        let (m, n) = self.shape();
+        let rows = to_range(rows, m);
+        let cols = to_range(cols, n);
+
+        Self {
+            mat: self.mat[rows]
+                .iter()
+                .map(|v| v.as_slice()[cols.clone()].iter().cloned().collect())
+                .collect(),
+            cols: cols.len(),
+        }
+    }
+
+    pub fn hstack(&self, other: &Self) -> Result<Self, LinAlgError> {
+        let (m, n) = self.shape();
+        let (om, on) = other.shape();
+        if m != om {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: (m, on),
+                found: (om, on),
+            });
+        }
+
+        Ok(Self {
+            mat: self
+                .mat
+                .iter()
+                .zip(other.iter())
+                .map(|(a, b)| a.iter().chain(b.iter()).cloned().collect())
+                .collect(),
+            cols: n + on,
+        })
+    }
+
+    pub fn vstack(&self, other: &Self) -> Result<Self, LinAlgError> {
+        let (_, n) = self.shape();
+        let (om, on) = other.shape();
+        if n != on {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: (om, n),
+                found: (om, on),
+            });
+        }
+
+        Ok(Self {
+            mat: self.mat.iter().chain(other.iter()).cloned().collect(),
+            cols: n,
+        })
+    }
+
+    pub fn insert_row(&mut self, index: usize, row: vec::Vec<T>) -> Result<(), LinAlgError> {
+        let (m, n) = self.shape();
+        if m != 0 && row.len() != n {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: (1, n),
+                found: (1, row.len()),
+            });
+        }
+
+        if m == 0 {
+            self.cols = row.len();
+        }
+        self.mat.insert(index, row);
+        Ok(())
+    }
+
+    pub fn remove_row(&mut self, index: usize) -> vec::Vec<T> {
+        self.mat.remove(index)
+    }
+
+    pub fn insert_col(&mut self, index: usize, col: vec::Vec<T>) -> Result<(), LinAlgError> {
+        let (m, _) = self.shape();
+        if col.len() != m {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: (m, 1),
+                found: (col.len(), 1),
+            });
+        }
+
+        for (v, x) in self.mat.iter_mut().zip(col.iter()) {
+            v.insert(index, x.clone());
+        }
+        self.cols += 1;
+        Ok(())
+    }
+
+    pub fn remove_col(&mut self, index: usize) -> vec::Vec<T> {
+        assert!(index < self.cols, "column index out of range");
+        self.cols -= 1;
+        self.mat.iter_mut().map(|v| v.remove(index)).collect()
+    }
+
+    // [A | b]
+    pub fn augment(&self, b: &vec::Vec<T>) -> Result<Self, LinAlgError> {
+        let mut mat = self.clone();
+        let (_, n) = self.shape();
+        mat.insert_col(n, b.clone())?;
+        Ok(mat)
+    }
+
+    // [A | I]
+    pub fn augment_identity(&self) -> Self
+    where
+        T: identities::One + identities::Zero,
+    {
+        let (m, _) = self.shape();
+        self.hstack(&Self::identity(m, m).unwrap())
+            .expect("identity has as many rows as the matrix")
+    }
+
+    pub fn pow(&self, mut k: u32) -> Result<Self, LinAlgError>
+    where
+        T: identities::Zero + identities::One + Mul<Output = T> + Add<Output = T>,
+    {
+        let (m, n) = self.shape();
+        if m != n {
+            return Err(LinAlgError::NotSquare { shape: (m, n) });
+        }
+
+        let mut result = Self::identity(m, n).unwrap();
+        let mut base = self.clone();
+        while k > 0 {
+            if k & 1 == 1 {
+                result = (&result * &base)?;
+            }
+            k >>= 1;
+            if k > 0 {
+                base = (&base * &base)?;
+            }
+        }
+
+        Ok(result)
     }
 
     pub fn resize(&mut self, m: usize, n: usize)
@@ -192,9 +723,51 @@ impl<T: Copy> Mat2d<T> {
             v.resize(n, T::zero())
         }
         self.mat.resize(m, vec::Vec::zeros(n));
+        self.cols = n;
     }
 }
 
+// Column of the first non-zero entry of each non-zero row of an echelon form
+pub(super) fn leading_columns<T: identities::Zero + Clone>(mat: &Mat2d<T>) -> Vec<usize> {
+    mat.iter()
+        .filter_map(|row| row.iter().position(|x| !x.is_zero()))
+        .collect()
+}
+
+// One solution of the homogeneous system per free column among the first n
+// columns of an RREF
+pub(super) fn kernel_basis<T: Field>(
+    rref: &Mat2d<T>,
+    pivots: &[usize],
+    n: usize,
+) -> Vec<vec::Vec<T>> {
+    (0..n)
+        .filter(|col| !pivots.contains(col))
+        .map(|free| {
+            let mut v = vec::Vec::zeros(n);
+            v[free] = T::one();
+            for (row, &col) in pivots.iter().enumerate() {
+                v[col] = -rref[row][free].clone();
+            }
+            v
+        })
+        .collect()
+}
+
+fn to_range<R: RangeBounds<usize>>(range: R, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&i) => i,
+        Bound::Excluded(&i) => i + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&i) => i + 1,
+        Bound::Excluded(&i) => i,
+        Bound::Unbounded => len,
+    };
+    start..end
+}
+
 impl<T: Debug> Debug for Mat2d<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -224,7 +797,290 @@ impl<T> IndexMut<usize> for Mat2d<T> {
     }
 }
 
-impl<T: identities::Zero + Copy, const M: usize, const N: usize> From<[[T; N]; M]> for Mat2d<T> {
+impl<T> Mul<&Mat2d<T>> for &Mat2d<T>
+where
+    T: identities::Zero + Mul<Output = T> + Add<Output = T> + Clone,
+{
+    type Output = Result<Mat2d<T>, LinAlgError>;
+
+    fn mul(self, rhs: &Mat2d<T>) -> Self::Output {
+        let (m, n) = self.shape();
+        let (rm, rn) = rhs.shape();
+        if n != rm {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: (n, rn),
+                found: (rm, rn),
+            });
+        }
+
+        let mut mat: Mat2d<T> = Mat2d::zeros(m, rn);
+        for i in 0..m {
+            for k in 0..n {
+                let a = &self[i][k];
+                for j in 0..rn {
+                    mat[i][j] = mat[i][j].clone() + a.clone() * rhs[k][j].clone();
+                }
+            }
+        }
+
+        Ok(mat)
+    }
+}
+
+impl<T> Mul<&vec::Vec<T>> for &Mat2d<T>
+where
+    T: identities::Zero + Mul<Output = T> + Add<Output = T> + Clone,
+{
+    type Output = Result<vec::Vec<T>, LinAlgError>;
+
+    fn mul(self, rhs: &vec::Vec<T>) -> Self::Output {
+        let (_, n) = self.shape();
+        if n != rhs.len() {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: (n, 1),
+                found: (rhs.len(), 1),
+            });
+        }
+
+        Ok(self.iter().map(|v| v.dot(rhs)).collect())
+    }
+}
+
+impl<T> Mul<&Mat2d<T>> for &vec::Vec<T>
+where
+    T: identities::Zero + Mul<Output = T> + Add<Output = T> + Clone,
+{
+    type Output = Result<vec::Vec<T>, LinAlgError>;
+
+    fn mul(self, rhs: &Mat2d<T>) -> Self::Output {
+        let (m, n) = rhs.shape();
+        if self.len() != m {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: (1, m),
+                found: (1, self.len()),
+            });
+        }
+
+        let mut vec: vec::Vec<T> = vec::Vec::zeros(n);
+        for (a, row) in self.iter().zip(rhs.iter()) {
+            for (x, b) in vec.iter_mut().zip(row.iter()) {
+                *x = x.clone() + a.clone() * b.clone();
+            }
+        }
+
+        Ok(vec)
+    }
+}
+
+macro_rules! forward_owned_product {
+    ($lhs:ty, $rhs:ty, $output:ty) => {
+        impl<T> Mul<$rhs> for $lhs
+        where
+            T: identities::Zero + Mul<Output = T> + Add<Output = T> + Clone,
+        {
+            type Output = Result<$output, LinAlgError>;
+
+            fn mul(self, rhs: $rhs) -> Self::Output {
+                &self * &rhs
+            }
+        }
+
+        impl<T> Mul<&$rhs> for $lhs
+        where
+            T: identities::Zero + Mul<Output = T> + Add<Output = T> + Clone,
+        {
+            type Output = Result<$output, LinAlgError>;
+
+            fn mul(self, rhs: &$rhs) -> Self::Output {
+                &self * rhs
+            }
+        }
+
+        impl<T> Mul<$rhs> for &$lhs
+        where
+            T: identities::Zero + Mul<Output = T> + Add<Output = T> + Clone,
+        {
+            type Output = Result<$output, LinAlgError>;
+
+            fn mul(self, rhs: $rhs) -> Self::Output {
+                self * &rhs
+            }
+        }
+    };
+}
+
+forward_owned_product!(Mat2d<T>, Mat2d<T>, Mat2d<T>);
+forward_owned_product!(Mat2d<T>, vec::Vec<T>, vec::Vec<T>);
+forward_owned_product!(vec::Vec<T>, Mat2d<T>, vec::Vec<T>);
+
+macro_rules! impl_elementwise_op {
+    ($op:ident, $method:ident, $op_assign:ident, $method_assign:ident, $name:literal) => {
+        impl<T> $op_assign<&Mat2d<T>> for Mat2d<T>
+        where
+            T: $op<Output = T> + Clone,
+        {
+            fn $method_assign(&mut self, rhs: &Mat2d<T>) {
+                assert!(
+                    self.shape() == rhs.shape(),
+                    concat!("two matrices in ", $name, " must have the same shape")
+                );
+
+                for (a, b) in self.mat.iter_mut().zip(rhs.iter()) {
+                    a.$method_assign(b);
+                }
+            }
+        }
+
+        impl<T> $op_assign<Mat2d<T>> for Mat2d<T>
+        where
+            T: $op<Output = T> + Clone,
+        {
+            fn $method_assign(&mut self, rhs: Mat2d<T>) {
+                self.$method_assign(&rhs)
+            }
+        }
+
+        impl<T> $op<&Mat2d<T>> for Mat2d<T>
+        where
+            T: $op<Output = T> + Clone,
+        {
+            type Output = Mat2d<T>;
+
+            fn $method(mut self, rhs: &Mat2d<T>) -> Self::Output {
+                self.$method_assign(rhs);
+                self
+            }
+        }
+
+        impl<T> $op<Mat2d<T>> for Mat2d<T>
+        where
+            T: $op<Output = T> + Clone,
+        {
+            type Output = Mat2d<T>;
+
+            fn $method(self, rhs: Mat2d<T>) -> Self::Output {
+                self.$method(&rhs)
+            }
+        }
+
+        impl<T> $op<&Mat2d<T>> for &Mat2d<T>
+        where
+            T: $op<Output = T> + Clone,
+        {
+            type Output = Mat2d<T>;
+
+            fn $method(self, rhs: &Mat2d<T>) -> Self::Output {
+                self.clone().$method(rhs)
+            }
+        }
+
+        impl<T> $op<Mat2d<T>> for &Mat2d<T>
+        where
+            T: $op<Output = T> + Clone,
+        {
+            type Output = Mat2d<T>;
+
+            fn $method(self, rhs: Mat2d<T>) -> Self::Output {
+                self.clone().$method(&rhs)
+            }
+        }
+    };
+}
+
+impl_elementwise_op!(Add, add, AddAssign, add_assign, "addition");
+impl_elementwise_op!(Sub, sub, SubAssign, sub_assign, "subtraction");
+
+macro_rules! impl_scalar_op {
+    ($op:ident, $method:ident, $op_assign:ident, $method_assign:ident) => {
+        impl<T> $op_assign<T> for Mat2d<T>
+        where
+            T: $op<Output = T> + Clone,
+        {
+            fn $method_assign(&mut self, rhs: T) {
+                for v in &mut self.mat {
+                    v.$method_assign(rhs.clone());
+                }
+            }
+        }
+
+        impl<T> $op<T> for Mat2d<T>
+        where
+            T: $op<Output = T> + Clone,
+        {
+            type Output = Mat2d<T>;
+
+            fn $method(mut self, rhs: T) -> Self::Output {
+                self.$method_assign(rhs);
+                self
+            }
+        }
+
+        impl<T> $op<T> for &Mat2d<T>
+        where
+            T: $op<Output = T> + Clone,
+        {
+            type Output = Mat2d<T>;
+
+            fn $method(self, rhs: T) -> Self::Output {
+                self.clone().$method(rhs)
+            }
+        }
+    };
+}
+
+impl_scalar_op!(Mul, mul, MulAssign, mul_assign);
+impl_scalar_op!(Div, div, DivAssign, div_assign);
+
+impl<T> Neg for Mat2d<T>
+where
+    T: Neg<Output = T> + Clone,
+{
+    type Output = Mat2d<T>;
+
+    fn neg(self) -> Self::Output {
+        Mat2d {
+            mat: self.mat.into_iter().map(|v| -v).collect(),
+            cols: self.cols,
+        }
+    }
+}
+
+impl<T> Neg for &Mat2d<T>
+where
+    T: Neg<Output = T> + Clone,
+{
+    type Output = Mat2d<T>;
+
+    fn neg(self) -> Self::Output {
+        -self.clone()
+    }
+}
+
+macro_rules! impl_scalar_lhs_mul {
+    ($($t:ty),*) => {
+        $(
+            impl Mul<&Mat2d<$t>> for $t {
+                type Output = Mat2d<$t>;
+
+                fn mul(self, rhs: &Mat2d<$t>) -> Self::Output {
+                    rhs * self
+                }
+            }
+
+            impl Mul<Mat2d<$t>> for $t {
+                type Output = Mat2d<$t>;
+
+                fn mul(self, rhs: Mat2d<$t>) -> Self::Output {
+                    rhs * self
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_lhs_mul!(f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl<T: identities::Zero + Clone, const M: usize, const N: usize> From<[[T; N]; M]> for Mat2d<T> {
     fn from(arr: [[T; N]; M]) -> Self {
         let mut mat = Self::zeros(M, N);
         for (vec, arr) in mat.iter_mut().zip(arr) {
@@ -233,3 +1089,201 @@ impl<T: identities::Zero + Copy, const M: usize, const N: usize> From<[[T; N]; M
         mat
     }
 }
+
+impl<T> From<Vec<vec::Vec<T>>> for Mat2d<T> {
+    fn from(mat: Vec<vec::Vec<T>>) -> Self {
+        assert!(
+            mat.windows(2).all(|w| w[0].len() == w[1].len()),
+            "all rows of a matrix must have the same length"
+        );
+        let cols = mat.first().map_or(0, vec::Vec::len);
+        Self { mat, cols }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::{rational::rational, vec, LinAlgError, Mat2d, Phase, PivotStrategy, RowOp};
+
+    #[test]
+    fn transpose_keeps_empty_dimensions() {
+        let a = Mat2d::<f64>::zeros(3, 0);
+        assert_eq!(a.transpose().shape(), (0, 3));
+        assert_eq!(a.transpose().transpose().shape(), (3, 0));
+
+        let b = Mat2d::from([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(b.transpose(), Mat2d::from([[1, 4], [2, 5], [3, 6]]));
+    }
+
+    #[test]
+    fn from_cols_of_empty_vectors() {
+        let cols = vec![vec::Vec::<f64>::zeros(0); 2];
+        assert_eq!(Mat2d::from_cols(cols).shape(), (0, 2));
+
+        let cols = vec![vec::Vec::from([1, 2]), vec::Vec::from([3, 4])];
+        assert_eq!(Mat2d::from_cols(cols), Mat2d::from([[1, 3], [2, 4]]));
+    }
+
+    #[test]
+    fn fraction_free_elimination_stays_integer() {
+        let a = Mat2d::from([[2i64, 4, 6], [3, 5, 7], [4, 6, 9]]);
+        let echelon = a.row_reduced_fraction_free();
+        assert_eq!(echelon, Mat2d::from([[2, 4, 6], [0, -1, -2], [0, 0, -1]]));
+
+        let singular = Mat2d::from([[1i64, 2], [-2, -4]]);
+        assert_eq!(
+            singular.row_reduced_fraction_free(),
+            Mat2d::from([[1, 2], [0, 0]])
+        );
+    }
+
+    #[test]
+    fn structural_edits() {
+        let mut a = Mat2d::from([[1, 2], [3, 4]]);
+        a.insert_col(1, vec::Vec::from([0, 0])).unwrap();
+        assert_eq!(a, Mat2d::from([[1, 0, 2], [3, 0, 4]]));
+        assert_eq!(a.remove_row(0), vec::Vec::from([1, 0, 2]));
+        assert_eq!(a.remove_col(2), vec::Vec::from([4]));
+        assert_eq!(a.shape(), (1, 2));
+        a.remove_row(0);
+        assert_eq!(a.shape(), (0, 2));
+
+        assert_eq!(
+            a.insert_col(0, vec::Vec::from([1])),
+            Err(LinAlgError::DimensionMismatch {
+                expected: (0, 1),
+                found: (1, 1)
+            })
+        );
+        let b = Mat2d::from([[1, 2]]);
+        assert!(b.hstack(&Mat2d::from([[1], [2]])).is_err());
+        assert_eq!(b.vstack(&b).unwrap().shape(), (2, 2));
+        assert_eq!(b.submatrix(.., 1..), Mat2d::from([[2]]));
+    }
+
+    #[test]
+    fn determinant_trace_shows_column_swaps() {
+        let a = Mat2d::from([[1, 2, 0], [3, 4, 1], [0, 1, 5]]).map(|&x| rational(x, 1));
+        let trace = a.determinant_verbose_with(PivotStrategy::Complete).unwrap();
+        assert_eq!(trace.determinant, rational(-11, 1));
+        assert_eq!(
+            trace.steps[..2]
+                .iter()
+                .map(|(_, op, _)| op.clone())
+                .collect::<Vec<_>>(),
+            [
+                RowOp::SwapCols { from: 0, to: 2 },
+                RowOp::Swap { from: 0, to: 2 },
+            ]
+        );
+        for (mat, _, factor) in &trace.steps {
+            assert_eq!(
+                factor.clone() * mat.determinant().unwrap(),
+                trace.determinant
+            );
+        }
+        let product = trace
+            .diagonal
+            .iter()
+            .fold(trace.sign.clone(), |acc, x| acc * x.clone());
+        assert_eq!(product, trace.determinant);
+    }
+
+    #[test]
+    fn products() {
+        let a = Mat2d::from([[1, 2, 3], [4, 5, 6]]);
+        let b = Mat2d::from([[1, 0], [0, 1], [1, 1]]);
+        assert_eq!((&a * &b).unwrap(), Mat2d::from([[4, 5], [10, 11]]));
+        assert_eq!(
+            (&a * &vec::Vec::from([1, 1, 1])).unwrap(),
+            vec::Vec::from([6, 15])
+        );
+        assert_eq!(
+            (vec::Vec::from([1, -1]) * a.clone()).unwrap(),
+            vec::Vec::from([-3, -3, -3])
+        );
+        assert_eq!(&a * 2, Mat2d::from([[2, 4, 6], [8, 10, 12]]));
+        assert!(matches!(
+            &a * &a,
+            Err(LinAlgError::DimensionMismatch {
+                expected: (3, 3),
+                found: (2, 3)
+            })
+        ));
+    }
+
+    #[test]
+    fn powers() {
+        let fib = Mat2d::from([[1, 1], [1, 0]]);
+        assert_eq!(fib.pow(10).unwrap(), Mat2d::from([[89, 55], [55, 34]]));
+        assert_eq!(fib.pow(0).unwrap(), Mat2d::identity(2, 2).unwrap());
+        assert!(matches!(
+            Mat2d::<i32>::zeros(2, 3).pow(2),
+            Err(LinAlgError::NotSquare { shape: (2, 3) })
+        ));
+    }
+
+    #[test]
+    fn elementwise_operators_and_row_updates() {
+        let a = Mat2d::from([[1, 2], [3, 4]]);
+        let b = Mat2d::from([[4, 3], [2, 1]]);
+        assert_eq!(&a + &b, Mat2d::from([[5, 5], [5, 5]]));
+        assert_eq!(&a - &b, Mat2d::from([[-3, -1], [1, 3]]));
+        assert_eq!(-&a, Mat2d::from([[-1, -2], [-3, -4]]));
+        assert_eq!(a.hadamard(&b), Mat2d::from([[4, 6], [6, 4]]));
+        assert_eq!(&b / 2, Mat2d::from([[2, 1], [1, 0]]));
+
+        let mut c = a.clone();
+        c.sub_scaled_row(0, 1, 3);
+        assert_eq!(c, Mat2d::from([[1, 2], [0, -2]]));
+    }
+
+    #[test]
+    fn determinant_by_elimination() {
+        let a = Mat2d::from([[0, 2, 1], [1, 1, 0], [2, 0, 3]]).map(|&x| rational(x, 1));
+        assert_eq!(a.determinant().unwrap(), rational(-8, 1));
+
+        // the zero pivot forces a row swap, which the factor records
+        let trace = a.determinant_verbose().unwrap();
+        assert_eq!(trace.steps[0].1, RowOp::Swap { from: 0, to: 1 });
+        assert_eq!(trace.steps[0].2, rational(-1, 1));
+        for (mat, _, factor) in &trace.steps {
+            assert_eq!(
+                factor.clone() * mat.determinant().unwrap(),
+                trace.determinant
+            );
+        }
+
+        let singular = Mat2d::from([[1, 2], [2, 4]]).map(|&x| rational(x, 1));
+        assert_eq!(singular.determinant().unwrap(), rational(0, 1));
+        assert!(matches!(
+            Mat2d::<f64>::zeros(2, 3).determinant(),
+            Err(LinAlgError::NotSquare { shape: (2, 3) })
+        ));
+    }
+
+    #[test]
+    fn rref_trace_runs_the_three_phases_in_order() {
+        let a = Mat2d::from([[2, 4, 2], [1, 3, 2], [0, 0, 0]]).map(|&x| rational(x, 1));
+        let trace = a.rref_verbose();
+        assert_eq!(
+            trace.rref,
+            Mat2d::from([[1, 0, -1], [0, 1, 1], [0, 0, 0]]).map(|&x| rational(x, 1))
+        );
+        assert_eq!(trace.pivots, vec![0, 1]);
+
+        let phases: Vec<Phase> = trace.steps.iter().map(|(_, _, phase)| *phase).collect();
+        assert!(phases.is_sorted_by_key(|phase| *phase as u8));
+        assert!([Phase::Forward, Phase::Normalize, Phase::Backward]
+            .iter()
+            .all(|phase| phases.contains(phase)));
+
+        // replaying the operations gives each recorded matrix
+        let mut mat = a.clone();
+        for (step, op, _) in &trace.steps {
+            op.apply(&mut mat);
+            assert_eq!(&mat, step);
+        }
+        assert_eq!(a.rref(), trace.rref);
+    }
+}
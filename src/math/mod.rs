@@ -1,6 +1,48 @@
+pub mod algebra;
+pub mod cofactor;
+pub mod complex;
+pub mod eigen;
+pub mod elementary;
+pub mod error;
 pub mod gauss;
+pub mod gf;
+pub mod gram_schmidt;
+pub mod inverse;
+pub mod least_squares;
+pub mod lu;
 pub mod mat;
+pub mod pivot;
+pub mod qr;
+pub mod rational;
+pub mod row_op;
+pub mod span;
+pub mod subspace;
+pub mod svd;
+pub mod tolerance;
 pub mod vec;
 
-pub use gauss::GaussElimIter;
-pub use mat::Mat2d;
+pub use algebra::{EuclideanRing, Field, OrderedField, RealField, Ring};
+pub use cofactor::{CofactorNode, ExpansionLine};
+pub use eigen::{Eigen, EigenSpace};
+pub use elementary::{elementary_matrices, ElementaryFactorization};
+pub use error::LinAlgError;
+pub use gauss::{GaussElimIter, SolutionSet};
+pub use gf::{Gf, Gf2Mat, Gf2Row};
+pub use gram_schmidt::{gram_schmidt, GramSchmidt, GramSchmidtStep};
+pub use inverse::InverseTrace;
+pub use least_squares::{LeastSquares, NormalEquations};
+pub use lu::LuDecomposition;
+pub use mat::{Echelon, Mat2d, Phase, RrefTrace};
+pub use num_complex::Complex;
+pub use pivot::{PivotStrategy, Pivoting};
+pub use qr::{Qr, QrMethod};
+pub use rational::{parse_decimal, Rational};
+pub use row_op::RowOp;
+pub use span::{
+    extend_to_basis, extend_to_basis_with, linear_independence, linear_independence_with,
+    span_basis, span_basis_with, span_membership, span_membership_with, Basis, Independence,
+    Reduction, SpanMembership,
+};
+pub use subspace::RankNullity;
+pub use svd::Svd;
+pub use tolerance::Tolerance;
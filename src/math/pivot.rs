@@ -0,0 +1,374 @@
+use std::ops::Range;
+
+use super::{
+    tolerance::{Scaled, ZeroTest},
+    vec, Field, Mat2d, OrderedField, RowOp, Tolerance,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PivotStrategy {
+    // The first non-zero entry, as when reducing by hand
+    #[default]
+    FirstNonZero,
+    // The entry of largest magnitude in the pivot column
+    Partial,
+    // The entry largest relative to the largest entry of its own row
+    ScaledPartial,
+    // The entry of largest magnitude in the whole remaining submatrix, moved
+    // into place by swapping columns as well as rows
+    Complete,
+    // ±1 if there is one, otherwise the entry of smallest magnitude, which
+    // keeps the numbers small in hand-worked examples
+    Simplest,
+}
+
+// How elimination picks pivots and decides what is zero. A bare
+// PivotStrategy converts to one that compares exactly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pivoting<T> {
+    pub strategy: PivotStrategy,
+    pub tolerance: Tolerance<T>,
+}
+
+impl<T: OrderedField> Pivoting<T> {
+    pub fn new(strategy: PivotStrategy, tolerance: Tolerance<T>) -> Self {
+        Self {
+            strategy,
+            tolerance,
+        }
+    }
+
+    // The zero test for eliminating mat, with column scales taken from it
+    pub(super) fn zero_test(&self, mat: &Mat2d<T>) -> Scaled<T> {
+        Scaled::new(mat, &self.tolerance)
+    }
+
+    // The same pivoting without column swaps, for reductions whose columns
+    // must stay in order. Complete pivoting then searches one column at a
+    // time, which is partial pivoting.
+    pub(super) fn in_column_order(self) -> Self {
+        let strategy = match self.strategy {
+            PivotStrategy::Complete => PivotStrategy::Partial,
+            strategy => strategy,
+        };
+        Self::new(strategy, self.tolerance)
+    }
+}
+
+impl<T: OrderedField> From<PivotStrategy> for Pivoting<T> {
+    fn from(strategy: PivotStrategy) -> Self {
+        Self::new(strategy, Tolerance::exact())
+    }
+}
+
+// Picks the pivot (row, col) among rows i.. and the given columns, or None if
+// they are all zero by the zero test
+pub(super) type Chooser<T> =
+    fn(&Mat2d<T>, usize, Range<usize>, &dyn ZeroTest<T>) -> Option<(usize, usize)>;
+
+impl PivotStrategy {
+    pub(super) fn chooser<T: OrderedField>(self) -> Chooser<T> {
+        match self {
+            Self::FirstNonZero => first_nonzero,
+            Self::Partial => partial,
+            Self::ScaledPartial => scaled_partial,
+            Self::Complete => complete,
+            Self::Simplest => simplest,
+        }
+    }
+}
+
+// The first column with a non-zero entry in rows i..
+pub(super) fn pivot_column<T: Field>(
+    mat: &Mat2d<T>,
+    i: usize,
+    cols: Range<usize>,
+    zero: &dyn ZeroTest<T>,
+) -> Option<usize> {
+    let (m, _) = mat.shape();
+    cols.into_iter()
+        .find(|&col| (i..m).any(|row| !zero.is_zero(&mat[row][col], col)))
+}
+
+// The row in i.. whose entry in col is best, keeping the first of equals
+pub(super) fn best_row<T, F>(
+    mat: &Mat2d<T>,
+    i: usize,
+    col: usize,
+    zero: &dyn ZeroTest<T>,
+    mut better: F,
+) -> usize
+where
+    T: Field,
+    F: FnMut(&T, &T) -> bool,
+{
+    let (m, _) = mat.shape();
+    (i + 1..m).fold(i, |best, row| {
+        let (x, y) = (&mat[row][col], &mat[best][col]);
+        if !zero.is_zero(x, col) && (zero.is_zero(y, col) || better(x, y)) {
+            row
+        } else {
+            best
+        }
+    })
+}
+
+pub(super) fn first_nonzero<T: Field>(
+    mat: &Mat2d<T>,
+    i: usize,
+    cols: Range<usize>,
+    zero: &dyn ZeroTest<T>,
+) -> Option<(usize, usize)> {
+    let col = pivot_column(mat, i, cols, zero)?;
+    Some((best_row(mat, i, col, zero, |_, _| false), col))
+}
+
+fn partial<T: OrderedField>(
+    mat: &Mat2d<T>,
+    i: usize,
+    cols: Range<usize>,
+    zero: &dyn ZeroTest<T>,
+) -> Option<(usize, usize)> {
+    let col = pivot_column(mat, i, cols, zero)?;
+    Some((best_row(mat, i, col, zero, |x, y| x.abs() > y.abs()), col))
+}
+
+fn scaled_partial<T: OrderedField>(
+    mat: &Mat2d<T>,
+    i: usize,
+    cols: Range<usize>,
+    zero: &dyn ZeroTest<T>,
+) -> Option<(usize, usize)> {
+    let col = pivot_column(mat, i, cols.clone(), zero)?;
+    let (m, _) = mat.shape();
+    // rows with a non-zero entry in col cannot have a zero scale
+    let scale = |row: usize| {
+        cols.clone()
+            .map(|j| mat[row][j].abs())
+            .fold(T::zero(), |acc, x| if x > acc { x } else { acc })
+    };
+    let best = (i..m)
+        .filter(|&row| !zero.is_zero(&mat[row][col], col))
+        .map(|row| (row, mat[row][col].abs() / scale(row)))
+        .fold(None, |best: Option<(usize, T)>, (row, ratio)| match best {
+            Some((_, ref r)) if *r >= ratio => best,
+            _ => Some((row, ratio)),
+        })?;
+    Some((best.0, col))
+}
+
+fn complete<T: OrderedField>(
+    mat: &Mat2d<T>,
+    i: usize,
+    cols: Range<usize>,
+    zero: &dyn ZeroTest<T>,
+) -> Option<(usize, usize)> {
+    let (m, _) = mat.shape();
+    let mut best: Option<(usize, usize)> = None;
+    for col in cols {
+        for row in i..m {
+            let x = &mat[row][col];
+            if zero.is_zero(x, col) {
+                continue;
+            }
+            match best {
+                Some((r, c)) if mat[r][c].abs() >= x.abs() => {}
+                _ => best = Some((row, col)),
+            }
+        }
+    }
+    best
+}
+
+fn simplest<T: OrderedField>(
+    mat: &Mat2d<T>,
+    i: usize,
+    cols: Range<usize>,
+    zero: &dyn ZeroTest<T>,
+) -> Option<(usize, usize)> {
+    let col = pivot_column(mat, i, cols, zero)?;
+    let row = best_row(mat, i, col, zero, |x, y| {
+        let (x, y) = (x.abs(), y.abs());
+        !y.is_one() && (x.is_one() || x < y)
+    });
+    Some((row, col))
+}
+
+// Moves the pivot for row i into place, searching columns col..limit. Zero
+// columns are skipped; a pivot further right is swapped into column col
+// (recorded in permutation) only when the columns in between are not zero.
+// Returns the pivot's row and column and the column swap performed, if any.
+// The row swap is left to swap_pivot_row, so that each swap is a step.
+pub(super) fn place_pivot<T: Field>(
+    mat: &mut Mat2d<T>,
+    i: usize,
+    col: usize,
+    limit: usize,
+    choose: Chooser<T>,
+    zero: &mut dyn ZeroTest<T>,
+    permutation: &mut [usize],
+) -> Option<(usize, usize, Option<RowOp<T>>)> {
+    let (m, _) = mat.shape();
+    let (row, c) = choose(mat, i, col..limit, zero)?;
+
+    if (col..c).all(|k| (i..m).all(|r| zero.is_zero(&mat[r][k], k))) {
+        return Some((row, c, None));
+    }
+    mat.swap_col(col, c);
+    zero.swap_cols(col, c);
+    permutation.swap(col, c);
+    Some((row, col, Some(RowOp::SwapCols { from: col, to: c })))
+}
+
+// Swaps the pivot found in row up to row i, returning the swap if it moved
+pub(super) fn swap_pivot_row<T: Field>(
+    mat: &mut Mat2d<T>,
+    i: usize,
+    row: usize,
+) -> Option<RowOp<T>> {
+    if row == i {
+        return None;
+    }
+    mat.swap_row(i, row);
+    Some(RowOp::Swap { from: i, to: row })
+}
+
+// R_to ← R_to - (a / p) R_from, where p = mat[from][col] is a pivot and
+// a = mat[to][col], returning the multiplier -(a / p). The entry in col comes
+// out exactly zero; of the other entries only those the pivot row changed can
+// be rounding residue, and those the zero test accepts are snapped to zero.
+pub(super) fn eliminate<T: Field>(
+    mat: &mut Mat2d<T>,
+    from: usize,
+    to: usize,
+    col: usize,
+    zero: &mut dyn ZeroTest<T>,
+) -> T {
+    let scale = mat[to][col].clone() / mat[from][col].clone();
+    zero.record(mat[to].as_slice());
+    mat.sub_scaled_row(from, to, scale.clone());
+    mat[to][col] = T::zero();
+    let (_, n) = mat.shape();
+    for k in 0..n {
+        let x = &mat[to][k];
+        // -0.0 is written as 0 too, so it never shows as "-0"
+        if x.is_zero() || (!mat[from][k].is_zero() && zero.is_zero(x, k)) {
+            mat[to][k] = T::zero();
+        }
+    }
+    -scale
+}
+
+// Writes every zero of a row as T::zero(), so a scaled row shows no "-0"
+pub(super) fn clear_zeros<T: Field>(row: &mut [T]) {
+    for x in row.iter_mut().filter(|x| x.is_zero()) {
+        *x = T::zero();
+    }
+}
+
+// Moves entry k of a vector over permuted columns to position
+// permutation[k], giving the vector over the original columns
+pub(super) fn unpermute<T: Clone>(v: &vec::Vec<T>, permutation: &[usize]) -> vec::Vec<T> {
+    let mut out = v.clone();
+    for (k, &col) in permutation.iter().enumerate() {
+        out[col] = v[k].clone();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{rational::rational, GaussElimIter, Rational, SolutionSet};
+
+    fn mat(rows: &[&[i64]]) -> Mat2d<Rational> {
+        Mat2d::from(
+            rows.iter()
+                .map(|row| row.iter().map(|&x| rational(x, 1)).collect())
+                .collect::<std::vec::Vec<vec::Vec<Rational>>>(),
+        )
+    }
+
+    fn first_op(a: &Mat2d<Rational>, strategy: PivotStrategy) -> RowOp<Rational> {
+        a.row_reduced_with(strategy).steps[0].1.clone()
+    }
+
+    #[test]
+    fn each_strategy_picks_its_pivot() {
+        let a = mat(&[&[2, 100, 0], &[1, 1, 0], &[-3, 0, 30]]);
+        assert!(matches!(
+            first_op(&a, PivotStrategy::FirstNonZero),
+            RowOp::AddMultiple { from: 0, .. }
+        ));
+        // |-3| is the largest in the column
+        assert_eq!(
+            first_op(&a, PivotStrategy::Partial),
+            RowOp::Swap { from: 0, to: 2 }
+        );
+        // 1 / 1 beats 2 / 100 and 3 / 30 relative to each row
+        assert_eq!(
+            first_op(&a, PivotStrategy::ScaledPartial),
+            RowOp::Swap { from: 0, to: 1 }
+        );
+        // 100 is the largest anywhere and is already in row 0
+        assert_eq!(
+            first_op(&a, PivotStrategy::Complete),
+            RowOp::SwapCols { from: 0, to: 1 }
+        );
+        assert_eq!(
+            first_op(&mat(&[&[4], &[2], &[3]]), PivotStrategy::Simplest),
+            RowOp::Swap { from: 0, to: 1 }
+        );
+        assert_eq!(
+            first_op(&mat(&[&[4], &[-1], &[3]]), PivotStrategy::Simplest),
+            RowOp::Swap { from: 0, to: 1 }
+        );
+    }
+
+    #[test]
+    fn strategies_agree_on_the_results() {
+        let a = mat(&[&[2, 100, 0], &[1, 1, 0], &[-3, 0, 30]]);
+        let b: vec::Vec<Rational> = [1, 2, 3].iter().map(|&x| rational(x, 1)).collect();
+        let determinant = a.determinant().unwrap();
+        let solution = a.solve(&b).unwrap();
+        for strategy in [
+            PivotStrategy::Partial,
+            PivotStrategy::ScaledPartial,
+            PivotStrategy::Complete,
+            PivotStrategy::Simplest,
+        ] {
+            assert_eq!(a.determinant_with(strategy).unwrap(), determinant);
+            assert_eq!(a.solve_with(&b, strategy).unwrap(), solution);
+            assert_eq!(a.rref_with(strategy).rref, a.rref());
+        }
+        assert!(matches!(solution, SolutionSet::Unique(_)));
+        assert_eq!(
+            a.row_reduced_with(PivotStrategy::Complete).col_permutation,
+            vec![1, 2, 0]
+        );
+    }
+
+    #[test]
+    fn iterator_yields_the_same_steps() {
+        let a = mat(&[&[2, 100, 0], &[1, 1, 0], &[-3, 0, 30]]);
+        let echelon = a.row_reduced_with(PivotStrategy::Complete);
+        let mut iter = GaussElimIter::with_strategy(a, PivotStrategy::Complete);
+        let steps: std::vec::Vec<_> = iter.by_ref().collect();
+        assert_eq!(steps.len(), echelon.steps.len());
+        for ((op, mat), (expected_mat, expected_op)) in steps.iter().zip(&echelon.steps) {
+            assert_eq!((op, mat), (expected_op, expected_mat));
+        }
+        assert_eq!(iter.col_permutation(), echelon.col_permutation.as_slice());
+    }
+
+    #[test]
+    fn column_order_drops_column_swaps_only() {
+        let complete: Pivoting<f64> = PivotStrategy::Complete.into();
+        assert_eq!(complete.in_column_order().strategy, PivotStrategy::Partial);
+        let scaled: Pivoting<f64> = PivotStrategy::ScaledPartial.into();
+        assert_eq!(
+            scaled.in_column_order().strategy,
+            PivotStrategy::ScaledPartial
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use super::{
+    gram_schmidt::{gram_schmidt, GramSchmidtStep},
+    vec, LinAlgError, Mat2d, RealField,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QrMethod {
+    GramSchmidt,
+    ModifiedGramSchmidt,
+    Householder,
+}
+
+#[derive(Debug, Clone)]
+pub struct Qr<T> {
+    pub q: Mat2d<T>,
+    pub r: Mat2d<T>,
+}
+
+// The classical Gram-Schmidt steps, with index the column of A
+pub type QrStep<T> = GramSchmidtStep<T>;
+
+#[derive(Debug, Clone)]
+pub struct QrTrace<T> {
+    pub qr: Qr<T>,
+    pub steps: Vec<QrStep<T>>,
+}
+
+// A column is treated as dependent once its remainder is lost in rounding
+pub(super) fn is_dependent<T: RealField>(remainder: T, original: T, m: usize) -> bool {
+    remainder <= T::epsilon() * T::from_usize(m) * original
+}
+
+impl<T: RealField> Mat2d<T> {
+    // Thin QR: Q is m x k and R is k x n with k = min(m, n). The Gram-Schmidt
+    // variants need m >= n and linearly independent columns.
+    pub fn qr(&self, method: QrMethod) -> Result<Qr<T>, LinAlgError> {
+        match method {
+            QrMethod::GramSchmidt => self.gram_schmidt_qr(|_| {}),
+            QrMethod::ModifiedGramSchmidt => self.modified_gram_schmidt_qr(),
+            QrMethod::Householder => Ok(self.householder_qr()),
+        }
+    }
+
+    pub fn qr_verbose(&self) -> Result<QrTrace<T>, LinAlgError> {
+        let mut steps = Vec::new();
+        let qr = self.gram_schmidt_qr(|step| steps.push(step))?;
+        Ok(QrTrace { qr, steps })
+    }
+
+    fn gram_schmidt_qr<F>(&self, mut on_step: F) -> Result<Qr<T>, LinAlgError>
+    where
+        F: FnMut(QrStep<T>),
+    {
+        let (m, n) = self.shape();
+        if m < n {
+            return Err(LinAlgError::LinearlyDependent { col: m });
+        }
+
+        let cols: Vec<vec::Vec<T>> = (0..n).map(|j| self.col(j)).collect();
+        let gs = gram_schmidt(&cols);
+        if let Some(&col) = gs.dependent.first() {
+            return Err(LinAlgError::LinearlyDependent { col });
+        }
+
+        let mut r = Self::zeros(n, n);
+        for step in gs.steps {
+            match &step {
+                GramSchmidtStep::Project {
+                    index, onto, coeff, ..
+                } => r[*onto][*index] = *coeff,
+                GramSchmidtStep::Normalize { index, norm, .. } => r[*index][*index] = *norm,
+                GramSchmidtStep::Dependent { .. } => {}
+            }
+            on_step(step);
+        }
+
+        Ok(Qr {
+            q: Self::from_cols(gs.basis),
+            r,
+        })
+    }
+
+    fn modified_gram_schmidt_qr(&self) -> Result<Qr<T>, LinAlgError> {
+        let (m, n) = self.shape();
+        if m < n {
+            return Err(LinAlgError::LinearlyDependent { col: m });
+        }
+
+        let mut vs: Vec<vec::Vec<T>> = (0..n).map(|j| self.col(j)).collect();
+        let norms: Vec<T> = vs.iter().map(vec::Vec::norm_l2).collect();
+        let mut r = Self::zeros(n, n);
+
+        for i in 0..n {
+            r[i][i] = vs[i].norm_l2();
+            if is_dependent(r[i][i], norms[i], m) {
+                return Err(LinAlgError::LinearlyDependent { col: i });
+            }
+            vs[i] /= r[i][i];
+
+            let (done, rest) = vs.split_at_mut(i + 1);
+            let q = &done[i];
+            for (j, v) in rest.iter_mut().enumerate() {
+                // modified Gram-Schmidt projects the partially reduced column
+                let j = i + 1 + j;
+                r[i][j] = q.dot(v);
+                v.sub_scaled(r[i][j], q);
+            }
+        }
+
+        Ok(Qr {
+            q: Self::from_cols(vs),
+            r,
+        })
+    }
+
+    fn householder_qr(&self) -> Qr<T> {
+        let (m, n) = self.shape();
+        let k = m.min(n);
+        let mut r = self.clone();
+        let mut q = Self::identity(m, m).unwrap();
+        let two = T::one() + T::one();
+
+        for c in 0..k.min(m.saturating_sub(1)) {
+            let x: vec::Vec<T> = (c..m).map(|i| r[i][c]).collect();
+            let x_norm = x.norm_l2();
+            if x_norm == T::zero() {
+                continue;
+            }
+
+            // reflect x onto -sign(x0) * |x| e1 to avoid cancellation
+            let alpha = if x[0] < T::zero() { x_norm } else { -x_norm };
+            let mut v = x;
+            v[0] = v[0] - alpha;
+            let v_norm = v.norm_l2();
+            if v_norm == T::zero() {
+                continue;
+            }
+            v /= v_norm;
+
+            // R <- (I - 2vv^T) R
+            for j in c..n {
+                let s = (c..m).fold(T::zero(), |acc, i| acc + v[i - c] * r[i][j]) * two;
+                for i in c..m {
+                    r[i][j] = r[i][j] - s * v[i - c];
+                }
+            }
+            // Q <- Q (I - 2vv^T)
+            for i in 0..m {
+                let s = (c..m).fold(T::zero(), |acc, j| acc + q[i][j] * v[j - c]) * two;
+                for j in c..m {
+                    q[i][j] = q[i][j] - s * v[j - c];
+                }
+            }
+            for i in c + 1..m {
+                r[i][c] = T::zero();
+            }
+        }
+
+        Qr {
+            q: q.submatrix(.., ..k),
+            r: r.submatrix(..k, ..),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Tolerance;
+
+    fn close(a: &Mat2d<f64>, b: &Mat2d<f64>) -> bool {
+        a.approx_eq(b, &Tolerance::new(0.0, 1e-12))
+    }
+
+    #[test]
+    fn every_method_factors_the_matrix() {
+        let a = Mat2d::from([[12.0, -51.0, 4.0], [6.0, 167.0, -68.0], [-4.0, 24.0, -41.0]]);
+        let identity = Mat2d::identity(3, 3).unwrap();
+        for method in [
+            QrMethod::GramSchmidt,
+            QrMethod::ModifiedGramSchmidt,
+            QrMethod::Householder,
+        ] {
+            let Qr { q, r } = a.qr(method).unwrap();
+            assert!(close(&(&q * &r).unwrap(), &a));
+            assert!(close(&(&q.transpose() * &q).unwrap(), &identity));
+            assert!((0..3).all(|i| (0..i).all(|j| r[i][j].abs() < 1e-9)));
+        }
+    }
+
+    #[test]
+    fn householder_handles_wide_and_dependent_matrices() {
+        let wide = Mat2d::from([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let Qr { q, r } = wide.qr(QrMethod::Householder).unwrap();
+        assert_eq!((q.shape(), r.shape()), ((2, 2), (2, 3)));
+        assert!(close(&(&q * &r).unwrap(), &wide));
+
+        let dependent = Mat2d::from([[1.0, 2.0], [2.0, 4.0], [3.0, 6.0]]);
+        assert!(dependent.qr(QrMethod::Householder).is_ok());
+        for method in [QrMethod::GramSchmidt, QrMethod::ModifiedGramSchmidt] {
+            assert!(matches!(
+                dependent.qr(method),
+                Err(LinAlgError::LinearlyDependent { col: 1 })
+            ));
+        }
+    }
+
+    #[test]
+    fn trace_fills_r() {
+        let a = Mat2d::from([[3.0, 1.0], [4.0, 2.0]]);
+        let trace = a.qr_verbose().unwrap();
+        // a projection and a normalization per column, less the first projection
+        assert_eq!(trace.steps.len(), 3);
+        assert!((trace.qr.r[0][0] - 5.0_f64).abs() < 1e-12);
+        assert!((trace.qr.r[0][1] - 2.2_f64).abs() < 1e-12);
+    }
+}
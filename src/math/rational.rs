@@ -0,0 +1,79 @@
+use num_bigint::BigInt;
+use num_rational::BigRational;
+
+// Arbitrary precision fraction, always kept in lowest terms
+pub type Rational = BigRational;
+
+pub fn rational(numer: i64, denom: i64) -> Rational {
+    Rational::new(BigInt::from(numer), BigInt::from(denom))
+}
+
+// Largest power of ten parse_decimal will build, so that text like "1e-999999"
+// cannot make it allocate without bound
+const MAX_SHIFT: u32 = 4096;
+
+// The exact value of a decimal such as "-0.125" or "1.5e-3", so that 0.1 is
+// 1/10 rather than the binary fraction nearest to it. None for anything else,
+// including "NaN" and "inf".
+pub fn parse_decimal(s: &str) -> Option<Rational> {
+    let (mantissa, exponent) = match s.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<i32>().ok()?),
+        None => (s, 0),
+    };
+    let (negative, digits) = match mantissa.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, mantissa.strip_prefix('+').unwrap_or(mantissa)),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if whole.is_empty() && fraction.is_empty()
+        || !whole
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let numer: BigInt = format!("{whole}{fraction}").parse().ok()?;
+    let numer = if negative { -numer } else { numer };
+    let shift = exponent.checked_sub(i32::try_from(fraction.len()).ok()?)?;
+    if shift.unsigned_abs() > MAX_SHIFT {
+        return None;
+    }
+    let power = BigInt::from(10).pow(shift.unsigned_abs());
+    Some(if shift < 0 {
+        Rational::new(numer, power)
+    } else {
+        Rational::from_integer(numer * power)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_decimal, rational};
+
+    #[test]
+    fn decimals_are_exact() {
+        assert_eq!(parse_decimal("0.1"), Some(rational(1, 10)));
+        assert_eq!(parse_decimal("-0.125"), Some(rational(-1, 8)));
+        assert_eq!(parse_decimal("+.5"), Some(rational(1, 2)));
+        assert_eq!(parse_decimal("1.5e-3"), Some(rational(3, 2000)));
+        assert_eq!(parse_decimal("2E3"), Some(rational(2000, 1)));
+        assert_eq!(parse_decimal("5."), Some(rational(5, 1)));
+    }
+
+    #[test]
+    fn rejects_what_is_not_a_decimal() {
+        for s in ["", "-", ".", "1e", "--1", "1.2.3", "NaN", "inf", "0x10"] {
+            assert_eq!(parse_decimal(s), None, "{s:?}");
+        }
+    }
+
+    #[test]
+    fn rejects_huge_exponents() {
+        assert_eq!(parse_decimal("1e-2147483648"), None);
+        assert_eq!(parse_decimal(".5e-2147483648"), None);
+        assert_eq!(parse_decimal("1e2147483647"), None);
+        assert!(parse_decimal("1e4000").is_some());
+    }
+}
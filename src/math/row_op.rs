@@ -0,0 +1,186 @@
+use std::fmt::{self, Display};
+
+use super::{Field, Mat2d, Ring};
+
+// An elementary row operation, or the column swap complete pivoting makes.
+// Rows and columns are 0-based here but rendered 1-based, as in R₁ ↔ R₂.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowOp<T> {
+    // R_from ↔ R_to
+    Swap { from: usize, to: usize },
+    // R_row ← scale R_row, with scale non-zero
+    Scale { scale: T, row: usize },
+    // R_to ← R_to + scale R_from
+    AddMultiple { scale: T, from: usize, to: usize },
+    // C_from ↔ C_to
+    SwapCols { from: usize, to: usize },
+}
+
+impl<T: Ring> RowOp<T> {
+    pub fn apply(&self, mat: &mut Mat2d<T>) {
+        match self {
+            Self::Swap { from, to } => mat.swap_row(*from, *to),
+            Self::Scale { scale, row } => mat[*row] *= scale.clone(),
+            Self::AddMultiple { scale, from, to } => mat.sub_scaled_row(*from, *to, -scale.clone()),
+            Self::SwapCols { from, to } => mat.swap_col(*from, *to),
+        }
+    }
+
+    // E such that E A is the result of applying the operation to A, or A E
+    // for a column swap
+    pub fn elementary_matrix(&self, n: usize) -> Mat2d<T> {
+        let mut e = Mat2d::identity(n, n).unwrap();
+        self.apply(&mut e);
+        e
+    }
+
+    // det(E), so that det(EA) = det(E) det(A)
+    pub fn determinant_factor(&self) -> T {
+        match self {
+            Self::Swap { .. } | Self::SwapCols { .. } => -T::one(),
+            Self::Scale { scale, .. } => scale.clone(),
+            Self::AddMultiple { .. } => T::one(),
+        }
+    }
+}
+
+impl<T: Field> RowOp<T> {
+    // The operation undoing this one
+    pub fn inverse(&self) -> Self {
+        match self {
+            Self::Swap { from, to } => Self::Swap {
+                from: *from,
+                to: *to,
+            },
+            Self::Scale { scale, row } => Self::Scale {
+                scale: scale.inv(),
+                row: *row,
+            },
+            Self::AddMultiple { scale, from, to } => Self::AddMultiple {
+                scale: -scale.clone(),
+                from: *from,
+                to: *to,
+            },
+            Self::SwapCols { from, to } => Self::SwapCols {
+                from: *from,
+                to: *to,
+            },
+        }
+    }
+}
+
+fn row(i: usize) -> String {
+    format!("R{}", subscript(i))
+}
+
+fn col(j: usize) -> String {
+    format!("C{}", subscript(j))
+}
+
+// The 1-based label of index i as subscript digits, so index 0 reads ₁
+pub fn subscript(i: usize) -> String {
+    const DIGITS: [char; 10] = ['₀', '₁', '₂', '₃', '₄', '₅', '₆', '₇', '₈', '₉'];
+    (i + 1)
+        .to_string()
+        .chars()
+        .map(|c| DIGITS[c.to_digit(10).unwrap() as usize])
+        .collect()
+}
+
+// The sign and magnitude of a coefficient in front of a row. The magnitude is
+// wrapped in parentheses when it is a fraction or a sum (e.g. a complex
+// number), and omitted when it is 1.
+fn coefficient<T: Display>(scale: &T) -> (bool, String) {
+    let c = scale.to_string();
+    let (negative, magnitude) = match c.strip_prefix('-') {
+        Some(rest) if !rest.contains(['+', '-']) => (true, rest),
+        _ => (false, c.as_str()),
+    };
+    let magnitude = if magnitude == "1" {
+        String::new()
+    } else if magnitude.contains(['+', '-', '/']) {
+        format!("({magnitude})")
+    } else {
+        magnitude.to_string()
+    };
+    (negative, magnitude)
+}
+
+impl<T: Display> Display for RowOp<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Swap { from, to } => write!(f, "{} ↔ {}", row(*from), row(*to)),
+            Self::Scale { scale, row: i } => {
+                let (negative, c) = coefficient(scale);
+                let sign = if negative { "−" } else { "" };
+                write!(f, "{} ← {sign}{c}{}", row(*i), row(*i))
+            }
+            Self::AddMultiple { scale, from, to } => {
+                let (negative, c) = coefficient(scale);
+                let sign = if negative { "−" } else { "+" };
+                write!(f, "{} ← {} {sign} {c}{}", row(*to), row(*to), row(*from))
+            }
+            Self::SwapCols { from, to } => write!(f, "{} ↔ {}", col(*from), col(*to)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::rational::rational;
+
+    #[test]
+    fn display_is_one_based() {
+        let swap: RowOp<i32> = RowOp::Swap { from: 0, to: 11 };
+        assert_eq!(swap.to_string(), "R₁ ↔ R₁₂");
+        let swap: RowOp<i32> = RowOp::SwapCols { from: 1, to: 2 };
+        assert_eq!(swap.to_string(), "C₂ ↔ C₃");
+        assert_eq!(RowOp::Scale { scale: 1, row: 0 }.to_string(), "R₁ ← R₁");
+        assert_eq!(RowOp::Scale { scale: -2, row: 1 }.to_string(), "R₂ ← −2R₂");
+        let op = RowOp::AddMultiple {
+            scale: rational(-1, 2),
+            from: 0,
+            to: 2,
+        };
+        assert_eq!(op.to_string(), "R₃ ← R₃ − (1/2)R₁");
+        let op = RowOp::AddMultiple {
+            scale: 3,
+            from: 1,
+            to: 0,
+        };
+        assert_eq!(op.to_string(), "R₁ ← R₁ + 3R₂");
+    }
+
+    #[test]
+    fn elementary_matrices_and_inverses() {
+        let a = Mat2d::from([[1, 2], [3, 4], [5, 6]]).map(|&x| rational(x, 1));
+        for op in [
+            RowOp::Swap { from: 0, to: 2 },
+            RowOp::Scale {
+                scale: rational(3, 1),
+                row: 1,
+            },
+            RowOp::AddMultiple {
+                scale: rational(-2, 1),
+                from: 0,
+                to: 1,
+            },
+        ] {
+            let mut applied = a.clone();
+            op.apply(&mut applied);
+            let e = op.elementary_matrix(3);
+            assert_eq!((&e * &a).unwrap(), applied);
+            assert_eq!(e.determinant().unwrap(), op.determinant_factor());
+
+            op.inverse().apply(&mut applied);
+            assert_eq!(applied, a);
+        }
+
+        // a column swap multiplies on the right
+        let op = RowOp::SwapCols { from: 0, to: 1 };
+        let mut applied = a.clone();
+        op.apply(&mut applied);
+        assert_eq!((&a * &op.elementary_matrix(2)).unwrap(), applied);
+    }
+}
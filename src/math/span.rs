@@ -0,0 +1,325 @@
+use super::{mat::kernel_basis, vec, Field, LinAlgError, Mat2d, OrderedField, Pivoting, RrefTrace};
+
+// The vectors as the columns of a matrix, reduced to RREF. The pivot columns
+// mark the vectors that are not combinations of the ones before them.
+#[derive(Debug, Clone)]
+pub struct Reduction<T> {
+    pub matrix: Mat2d<T>,
+    pub trace: RrefTrace<T>,
+    pub pivot_columns: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Independence<T> {
+    pub independent: bool,
+    // Vectors that are combinations of the vectors before them
+    pub dependent: Vec<usize>,
+    // Coefficients c with Σ c_i v_i = 0, one per dependent vector
+    pub relations: Vec<vec::Vec<T>>,
+    pub reduction: Reduction<T>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SpanMembership<T> {
+    // c with Σ c_i v_i = target, or None if target is outside the span
+    pub coefficients: Option<vec::Vec<T>>,
+    // Reduction of [v_1 ... v_k | target]
+    pub reduction: Reduction<T>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Basis<T> {
+    pub basis: Vec<vec::Vec<T>>,
+    // Position of each basis vector among the vectors that were reduced
+    pub indices: Vec<usize>,
+    pub reduction: Reduction<T>,
+}
+
+// Reduces the vectors as columns with the given Gauss-Jordan, which must not
+// reorder columns: the order says which vectors come before which
+fn reduce<T: Field, F>(vectors: &[vec::Vec<T>], n: usize, rref: F) -> Reduction<T>
+where
+    F: FnOnce(&Mat2d<T>) -> RrefTrace<T>,
+{
+    assert!(
+        vectors.iter().all(|v| v.len() == n),
+        "all vectors must have the same length"
+    );
+    let matrix = if vectors.is_empty() {
+        Mat2d::zeros(n, 0)
+    } else {
+        Mat2d::from_cols(vectors.to_vec())
+    };
+
+    let trace = rref(&matrix);
+    Reduction {
+        pivot_columns: trace.pivots.clone(),
+        trace,
+        matrix,
+    }
+}
+
+// Gauss-Jordan with the pivoting's tolerance, keeping the vectors in order
+fn rref_in_order<T: OrderedField>(pivoting: Pivoting<T>) -> impl FnOnce(&Mat2d<T>) -> RrefTrace<T> {
+    let pivoting = pivoting.in_column_order();
+    move |mat| mat.rref_with(pivoting)
+}
+
+fn dimension<T>(vectors: &[vec::Vec<T>]) -> usize {
+    vectors.first().map_or(0, vec::Vec::len)
+}
+
+pub fn linear_independence<T: Field>(vectors: &[vec::Vec<T>]) -> Independence<T> {
+    independence(reduce(vectors, dimension(vectors), Mat2d::rref_verbose))
+}
+
+// With a tolerance, vectors that are combinations of the ones before them up
+// to rounding count as dependent
+pub fn linear_independence_with<T: OrderedField>(
+    vectors: &[vec::Vec<T>],
+    pivoting: impl Into<Pivoting<T>>,
+) -> Independence<T> {
+    let rref = rref_in_order(pivoting.into());
+    independence(reduce(vectors, dimension(vectors), rref))
+}
+
+fn independence<T: Field>(reduction: Reduction<T>) -> Independence<T> {
+    let (_, k) = reduction.matrix.shape();
+    let dependent: Vec<usize> = (0..k)
+        .filter(|i| !reduction.pivot_columns.contains(i))
+        .collect();
+
+    Independence {
+        independent: dependent.is_empty(),
+        relations: kernel_basis(&reduction.trace.rref, &reduction.pivot_columns, k),
+        dependent,
+        reduction,
+    }
+}
+
+pub fn span_membership<T: Field>(
+    vectors: &[vec::Vec<T>],
+    target: &vec::Vec<T>,
+) -> Result<SpanMembership<T>, LinAlgError> {
+    membership(vectors, target, Mat2d::rref_verbose)
+}
+
+// With a tolerance, a target off the span only by rounding is in it
+pub fn span_membership_with<T: OrderedField>(
+    vectors: &[vec::Vec<T>],
+    target: &vec::Vec<T>,
+    pivoting: impl Into<Pivoting<T>>,
+) -> Result<SpanMembership<T>, LinAlgError> {
+    membership(vectors, target, rref_in_order(pivoting.into()))
+}
+
+fn membership<T: Field, F>(
+    vectors: &[vec::Vec<T>],
+    target: &vec::Vec<T>,
+    rref: F,
+) -> Result<SpanMembership<T>, LinAlgError>
+where
+    F: FnOnce(&Mat2d<T>) -> RrefTrace<T>,
+{
+    let n = target.len();
+    if let Some(v) = vectors.iter().find(|v| v.len() != n) {
+        return Err(LinAlgError::DimensionMismatch {
+            expected: (n, 1),
+            found: (v.len(), 1),
+        });
+    }
+
+    let mut augmented = vectors.to_vec();
+    augmented.push(target.clone());
+    let reduction = reduce(&augmented, n, rref);
+
+    // the RREF of [v_1 ... v_k | target] already holds the solution: target
+    // is outside the span when its column has a pivot, and otherwise setting
+    // the free coefficients to zero leaves the last column at the pivots
+    let k = vectors.len();
+    let coefficients = (!reduction.pivot_columns.contains(&k)).then(|| {
+        let mut c = vec::Vec::zeros(k);
+        for (row, &col) in reduction.pivot_columns.iter().enumerate() {
+            c[col] = reduction.trace.rref[row][k].clone();
+        }
+        c
+    });
+
+    Ok(SpanMembership {
+        coefficients,
+        reduction,
+    })
+}
+
+// The vectors at the pivot columns span the same space as all of them
+pub fn span_basis<T: Field>(vectors: &[vec::Vec<T>]) -> Basis<T> {
+    basis(
+        vectors,
+        reduce(vectors, dimension(vectors), Mat2d::rref_verbose),
+    )
+}
+
+pub fn span_basis_with<T: OrderedField>(
+    vectors: &[vec::Vec<T>],
+    pivoting: impl Into<Pivoting<T>>,
+) -> Basis<T> {
+    let rref = rref_in_order(pivoting.into());
+    basis(vectors, reduce(vectors, dimension(vectors), rref))
+}
+
+fn basis<T: Field>(vectors: &[vec::Vec<T>], reduction: Reduction<T>) -> Basis<T> {
+    Basis {
+        basis: reduction
+            .pivot_columns
+            .iter()
+            .map(|&i| vectors[i].clone())
+            .collect(),
+        indices: reduction.pivot_columns.clone(),
+        reduction,
+    }
+}
+
+// Appends e_1, ..., e_n after the vectors and keeps the pivot columns. Indices
+// past the input refer to e_(index - k) for k input vectors.
+pub fn extend_to_basis<T: Field>(
+    vectors: &[vec::Vec<T>],
+    n: usize,
+) -> Result<Basis<T>, LinAlgError> {
+    extend(vectors, n, span_basis)
+}
+
+// With a tolerance, an input vector that is a combination of the ones before
+// it up to rounding is reported as dependent
+pub fn extend_to_basis_with<T: OrderedField>(
+    vectors: &[vec::Vec<T>],
+    n: usize,
+    pivoting: impl Into<Pivoting<T>>,
+) -> Result<Basis<T>, LinAlgError> {
+    let pivoting = pivoting.into();
+    extend(vectors, n, |candidates| {
+        span_basis_with(candidates, pivoting)
+    })
+}
+
+fn extend<T: Field, F>(
+    vectors: &[vec::Vec<T>],
+    n: usize,
+    span_basis: F,
+) -> Result<Basis<T>, LinAlgError>
+where
+    F: FnOnce(&[vec::Vec<T>]) -> Basis<T>,
+{
+    if let Some(v) = vectors.iter().find(|v| v.len() != n) {
+        return Err(LinAlgError::DimensionMismatch {
+            expected: (n, 1),
+            found: (v.len(), 1),
+        });
+    }
+
+    let mut candidates = vectors.to_vec();
+    candidates.extend((0..n).map(|i| {
+        let mut e = vec::Vec::zeros(n);
+        e[i] = T::one();
+        e
+    }));
+    let basis = span_basis(&candidates);
+
+    // every input vector must survive as a pivot column
+    if let Some(col) = (0..vectors.len()).find(|i| !basis.indices.contains(i)) {
+        return Err(LinAlgError::LinearlyDependent { col });
+    }
+    Ok(basis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{rational::rational, PivotStrategy, Rational, Tolerance};
+    use super::*;
+
+    fn vectors(rows: &[&[i64]]) -> Vec<vec::Vec<Rational>> {
+        rows.iter()
+            .map(|v| v.iter().map(|&x| rational(x, 1)).collect::<Vec<_>>().into())
+            .collect()
+    }
+
+    #[test]
+    fn dependent_vectors_and_their_relation() {
+        let v = vectors(&[&[1, 0, 1], &[0, 1, 1], &[1, 1, 2]]);
+        let report = linear_independence(&v);
+        assert!(!report.independent);
+        assert_eq!(report.dependent, vec![2]);
+        assert_eq!(report.relations.len(), 1);
+
+        let c = &report.relations[0];
+        let combination = v.iter().zip(c.as_slice()).fold(
+            vec![rational(0, 1); 3],
+            |acc: Vec<Rational>, (vj, cj)| {
+                acc.into_iter()
+                    .zip(vj.as_slice())
+                    .map(|(a, x)| a + cj.clone() * x.clone())
+                    .collect()
+            },
+        );
+        assert!(combination.iter().all(|x| *x == rational(0, 1)));
+    }
+
+    #[test]
+    fn membership_gives_coefficients() {
+        let v = vectors(&[&[1, 0, 1], &[0, 1, 1]]);
+        let inside = span_membership(&v, &vectors(&[&[2, 3, 5]])[0]).unwrap();
+        assert_eq!(
+            inside.coefficients.unwrap().as_slice(),
+            &[rational(2, 1), rational(3, 1)]
+        );
+        let outside = span_membership(&v, &vectors(&[&[0, 0, 1]])[0]).unwrap();
+        assert!(outside.coefficients.is_none());
+        assert!(span_membership(&v, &vectors(&[&[1, 2]])[0]).is_err());
+    }
+
+    #[test]
+    fn extending_keeps_the_inputs() {
+        let basis = extend_to_basis(&vectors(&[&[1, 1, 0]]), 3).unwrap();
+        assert_eq!(basis.indices, vec![0, 1, 3]);
+        assert!(matches!(
+            extend_to_basis(&vectors(&[&[1, 1], &[2, 2]]), 2),
+            Err(LinAlgError::LinearlyDependent { col: 1 })
+        ));
+    }
+
+    #[test]
+    fn empty_vectors_keep_their_length() {
+        let report = linear_independence(&vectors(&[&[], &[]]));
+        assert_eq!(report.reduction.matrix.shape(), (0, 2));
+        assert_eq!(report.dependent, vec![0, 1]);
+    }
+
+    #[test]
+    fn tolerance_agrees_with_rank_with() {
+        let v: Vec<vec::Vec<f64>> = vec![
+            vec![0.1, 0.4, 0.7].into(),
+            vec![0.2, 0.5, 0.8].into(),
+            vec![0.3, 0.6, 0.9].into(),
+        ];
+        for strategy in [PivotStrategy::Partial, PivotStrategy::Complete] {
+            let pivoting = Pivoting::new(strategy, Tolerance::default());
+            let report = linear_independence_with(&v, pivoting.clone());
+            assert!(!report.independent);
+            assert_eq!(report.dependent, vec![2]);
+            assert_eq!(span_basis_with(&v, pivoting.clone()).indices, vec![0, 1]);
+            assert!(extend_to_basis_with(&v, 3, pivoting.clone()).is_err());
+            assert!(span_membership_with(&v[..2], &v[2], pivoting)
+                .unwrap()
+                .coefficients
+                .is_some());
+        }
+    }
+
+    #[test]
+    fn basis_keeps_the_first_of_dependent_vectors() {
+        let v = vectors(&[&[0, 0], &[1, 2], &[2, 4], &[0, 1]]);
+        let basis = span_basis(&v);
+        assert_eq!(basis.indices, vec![1, 3]);
+        assert_eq!(basis.basis, vec![v[1].clone(), v[3].clone()]);
+        assert_eq!(span_basis::<Rational>(&[]).basis.len(), 0);
+    }
+}
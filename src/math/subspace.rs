@@ -0,0 +1,254 @@
+use super::{
+    mat::kernel_basis,
+    pivot::{first_nonzero, unpermute},
+    tolerance::Exact,
+    vec, Field, Mat2d, OrderedField, Pivoting, RrefTrace,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankNullity {
+    pub rank: usize,
+    pub nullity: usize,
+    // number of columns
+    pub n: usize,
+    pub pivot_columns: Vec<usize>,
+    pub free_columns: Vec<usize>,
+}
+
+impl RankNullity {
+    // rank + nullity = n, with the nullity counted from the null space basis
+    // actually built
+    pub fn holds(&self) -> bool {
+        self.rank + self.nullity == self.n
+    }
+}
+
+impl<T: Field> Mat2d<T> {
+    // Exact Gauss-Jordan, which each subspace below reads from
+    fn reduction(&self) -> RrefTrace<T> {
+        let (_, n) = self.shape();
+        self.gauss_jordan(first_nonzero, &mut Exact, n, |_, _, _| {})
+    }
+
+    // Gauss-Jordan with the given pivoting. With a tolerance, columns that
+    // are dependent up to rounding count as dependent, so every report below
+    // agrees with rank_with.
+    fn reduction_with(&self, pivoting: impl Into<Pivoting<T>>) -> RrefTrace<T>
+    where
+        T: OrderedField,
+    {
+        let pivoting = pivoting.into();
+        let (_, n) = self.shape();
+        let mut zero = pivoting.zero_test(self);
+        self.gauss_jordan(pivoting.strategy.chooser(), &mut zero, n, |_, _, _| {})
+    }
+
+    pub fn pivot_columns(&self) -> Vec<usize> {
+        pivot_columns(&self.reduction())
+    }
+
+    // With complete pivoting these are the columns it picked, in increasing
+    // order, which need not be the leftmost independent ones
+    pub fn pivot_columns_with(&self, pivoting: impl Into<Pivoting<T>>) -> Vec<usize>
+    where
+        T: OrderedField,
+    {
+        pivot_columns(&self.reduction_with(pivoting))
+    }
+
+    pub fn free_columns(&self) -> Vec<usize> {
+        free_columns(&self.reduction())
+    }
+
+    pub fn free_columns_with(&self, pivoting: impl Into<Pivoting<T>>) -> Vec<usize>
+    where
+        T: OrderedField,
+    {
+        free_columns(&self.reduction_with(pivoting))
+    }
+
+    // Basis of {x : Ax = 0}, one vector per free column of the RREF
+    pub fn null_space(&self) -> Vec<vec::Vec<T>> {
+        null_space(&self.reduction())
+    }
+
+    // The vectors are in the original variable order whatever columns
+    // complete pivoting moved
+    pub fn null_space_with(&self, pivoting: impl Into<Pivoting<T>>) -> Vec<vec::Vec<T>>
+    where
+        T: OrderedField,
+    {
+        null_space(&self.reduction_with(pivoting))
+    }
+
+    // The pivot columns of A itself (not of its RREF) span its column space
+    pub fn column_space(&self) -> Vec<vec::Vec<T>> {
+        self.pivot_columns()
+            .into_iter()
+            .map(|col| self.col(col))
+            .collect()
+    }
+
+    pub fn column_space_with(&self, pivoting: impl Into<Pivoting<T>>) -> Vec<vec::Vec<T>>
+    where
+        T: OrderedField,
+    {
+        self.pivot_columns_with(pivoting)
+            .into_iter()
+            .map(|col| self.col(col))
+            .collect()
+    }
+
+    // Row operations preserve the row space, so the non-zero rows of the RREF
+    // are a basis for it
+    pub fn row_space(&self) -> Vec<vec::Vec<T>> {
+        row_space(&self.reduction())
+    }
+
+    pub fn row_space_with(&self, pivoting: impl Into<Pivoting<T>>) -> Vec<vec::Vec<T>>
+    where
+        T: OrderedField,
+    {
+        row_space(&self.reduction_with(pivoting))
+    }
+
+    // Basis of {y : yᵀA = 0}
+    pub fn left_null_space(&self) -> Vec<vec::Vec<T>> {
+        self.transpose().null_space()
+    }
+
+    pub fn left_null_space_with(&self, pivoting: impl Into<Pivoting<T>>) -> Vec<vec::Vec<T>>
+    where
+        T: OrderedField,
+    {
+        self.transpose().null_space_with(pivoting)
+    }
+
+    // From a single reduction
+    pub fn rank_nullity(&self) -> RankNullity {
+        rank_nullity(&self.reduction())
+    }
+
+    pub fn rank_nullity_with(&self, pivoting: impl Into<Pivoting<T>>) -> RankNullity
+    where
+        T: OrderedField,
+    {
+        rank_nullity(&self.reduction_with(pivoting))
+    }
+}
+
+// The readings below take a reduction of AQ, where column j of AQ is column
+// col_permutation[j] of A, and answer for A
+
+fn pivot_columns<T>(trace: &RrefTrace<T>) -> Vec<usize> {
+    let mut cols: Vec<usize> = trace
+        .pivots
+        .iter()
+        .map(|&col| trace.col_permutation[col])
+        .collect();
+    cols.sort_unstable();
+    cols
+}
+
+fn free_columns<T>(trace: &RrefTrace<T>) -> Vec<usize> {
+    let pivots = pivot_columns(trace);
+    (0..trace.col_permutation.len())
+        .filter(|col| !pivots.contains(col))
+        .collect()
+}
+
+fn null_space<T: Field>(trace: &RrefTrace<T>) -> Vec<vec::Vec<T>> {
+    let n = trace.col_permutation.len();
+    kernel_basis(&trace.rref, &trace.pivots, n)
+        .iter()
+        .map(|v| unpermute(v, &trace.col_permutation))
+        .collect()
+}
+
+fn row_space<T: Field>(trace: &RrefTrace<T>) -> Vec<vec::Vec<T>> {
+    (0..trace.pivots.len())
+        .map(|row| unpermute(&trace.rref.row(row), &trace.col_permutation))
+        .collect()
+}
+
+fn rank_nullity<T: Field>(trace: &RrefTrace<T>) -> RankNullity {
+    RankNullity {
+        rank: trace.pivots.len(),
+        nullity: null_space(trace).len(),
+        n: trace.col_permutation.len(),
+        pivot_columns: pivot_columns(trace),
+        free_columns: free_columns(trace),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::{
+        rational::rational, vec, Mat2d, PivotStrategy, Pivoting, Rational, Tolerance,
+    };
+
+    fn mat(rows: &[&[i64]]) -> Mat2d<Rational> {
+        Mat2d::from(
+            rows.iter()
+                .map(|row| row.iter().map(|&x| rational(x, 1)).collect())
+                .collect::<Vec<vec::Vec<Rational>>>(),
+        )
+    }
+
+    fn is_zero(v: &vec::Vec<Rational>) -> bool {
+        v.iter().all(|x| *x == rational(0, 1))
+    }
+
+    #[test]
+    fn subspaces_of_a_rank_two_matrix() {
+        let a = mat(&[&[1, 2, 0, 1], &[2, 4, 1, 1], &[3, 6, 1, 2]]);
+        assert_eq!(a.pivot_columns(), [0, 2]);
+        assert_eq!(a.free_columns(), [1, 3]);
+        assert_eq!(a.column_space(), vec![a.col(0), a.col(2)]);
+        assert_eq!(a.row_space().len(), 2);
+
+        let null = a.null_space();
+        assert_eq!(null.len(), 2);
+        for v in &null {
+            assert!(is_zero(&(&a * v).unwrap()));
+        }
+
+        let left = a.left_null_space();
+        assert_eq!(left.len(), 1);
+        assert!(is_zero(&(&left[0] * &a).unwrap()));
+    }
+
+    #[test]
+    fn tolerance_agrees_with_rank_with() {
+        let a = Mat2d::from([[0.1, 0.2, 0.3], [0.4, 0.5, 0.6], [0.7, 0.8, 0.9]]);
+        for strategy in [PivotStrategy::Partial, PivotStrategy::Complete] {
+            let pivoting = Pivoting::new(strategy, Tolerance::default());
+            let report = a.rank_nullity_with(pivoting.clone());
+            assert_eq!(report.rank, a.rank_with(pivoting.clone()));
+            assert_eq!(report.rank, 2);
+            assert_eq!(report.nullity, 1);
+            assert_eq!(a.column_space_with(pivoting.clone()).len(), 2);
+            assert_eq!(a.row_space_with(pivoting.clone()).len(), 2);
+            assert_eq!(a.left_null_space_with(pivoting.clone()).len(), 1);
+
+            let null = a.null_space_with(pivoting);
+            let residual = (&a * &null[0]).unwrap();
+            assert!(residual.norm_inf() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn rank_nullity_counts_the_null_space() {
+        let a = mat(&[&[1, 2, 3], &[2, 4, 6]]);
+        let report = a.rank_nullity();
+        assert_eq!(report.rank, 1);
+        assert_eq!(report.nullity, 2);
+        assert_eq!(report.pivot_columns, [0]);
+        assert_eq!(report.free_columns, [1, 2]);
+        assert!(report.holds());
+
+        let invertible = mat(&[&[1, 1], &[0, 1]]);
+        assert!(invertible.null_space().is_empty());
+        assert_eq!(invertible.rank_nullity().rank, 2);
+    }
+}
@@ -0,0 +1,273 @@
+use super::{vec, LinAlgError, Mat2d, RealField};
+
+const MAX_SWEEPS: usize = 60;
+
+// Thin SVD: for an m x n matrix with k = min(m, n), U is m x k, Σ holds k
+// singular values in descending order and Vᵀ is k x n.
+#[derive(Debug, Clone)]
+pub struct Svd<T> {
+    pub u: Mat2d<T>,
+    pub sigma: vec::Vec<T>,
+    pub vt: Mat2d<T>,
+}
+
+impl<T: RealField> Svd<T> {
+    pub fn sigma_matrix(&self) -> Mat2d<T> {
+        let k = self.sigma.len();
+        let mut mat = Mat2d::zeros(k, k);
+        for i in 0..k {
+            mat[i][i] = self.sigma[i];
+        }
+        mat
+    }
+
+    // U Σ Vᵀ
+    pub fn compose(&self) -> Mat2d<T> {
+        let (m, _) = self.u.shape();
+        let (_, n) = self.vt.shape();
+        let mut mat = Mat2d::zeros(m, n);
+        for (l, &s) in self.sigma.iter().enumerate() {
+            for i in 0..m {
+                let a = self.u[i][l] * s;
+                for j in 0..n {
+                    mat[i][j] = mat[i][j] + a * self.vt[l][j];
+                }
+            }
+        }
+        mat
+    }
+
+    // Keep only the k largest singular triplets
+    pub fn truncate(&self, k: usize) -> Self {
+        let k = k.min(self.sigma.len());
+        Self {
+            u: self.u.submatrix(.., ..k),
+            sigma: self.sigma.as_slice()[..k].iter().copied().collect(),
+            vt: self.vt.submatrix(..k, ..),
+        }
+    }
+
+    pub fn numerical_rank(&self, tol: T) -> usize {
+        self.sigma.iter().filter(|&&s| s > tol).count()
+    }
+
+    // max(m, n) * eps * σ_max, the usual cutoff for "numerically zero"
+    pub fn default_tolerance(&self) -> T {
+        let (m, _) = self.u.shape();
+        let (_, n) = self.vt.shape();
+        let largest = self.sigma.get(0).copied().unwrap_or_else(T::zero);
+        T::from_usize(m.max(n)) * T::epsilon() * largest
+    }
+
+    pub fn condition_number(&self) -> T {
+        match (self.sigma.get(0), self.sigma.iter().last()) {
+            (Some(&largest), Some(&smallest)) if smallest > T::zero() => largest / smallest,
+            _ => T::infinity(),
+        }
+    }
+
+    pub fn pseudo_inverse(&self) -> Mat2d<T> {
+        let (m, _) = self.u.shape();
+        let (_, n) = self.vt.shape();
+        let tol = self.default_tolerance();
+
+        // V Σ⁺ Uᵀ
+        let mut mat = Mat2d::zeros(n, m);
+        for (l, &s) in self.sigma.iter().enumerate() {
+            if s <= tol {
+                continue;
+            }
+            for i in 0..n {
+                let a = self.vt[l][i] / s;
+                for j in 0..m {
+                    mat[i][j] = mat[i][j] + a * self.u[j][l];
+                }
+            }
+        }
+        mat
+    }
+}
+
+impl<T: RealField> Mat2d<T> {
+    // One-sided (Hestenes) Jacobi SVD
+    pub fn svd(&self) -> Result<Svd<T>, LinAlgError> {
+        let (m, n) = self.shape();
+        if m < n {
+            let svd = self.transpose().svd()?;
+            return Ok(Svd {
+                u: svd.vt.transpose(),
+                sigma: svd.sigma,
+                vt: svd.u.transpose(),
+            });
+        }
+
+        // orthogonalize the columns of A by plane rotations, accumulating V
+        let mut cols: Vec<vec::Vec<T>> = (0..n).map(|j| self.col(j)).collect();
+        let mut v: Vec<vec::Vec<T>> = (0..n)
+            .map(|j| {
+                let mut e = vec::Vec::zeros(n);
+                e[j] = T::one();
+                e
+            })
+            .collect();
+        let two = T::one() + T::one();
+
+        let mut converged = false;
+        for _ in 0..MAX_SWEEPS {
+            let mut rotated = false;
+            for p in 0..n {
+                for q in p + 1..n {
+                    let alpha = cols[p].dot(&cols[p]);
+                    let beta = cols[q].dot(&cols[q]);
+                    let gamma = cols[p].dot(&cols[q]);
+                    if gamma.abs() <= T::epsilon() * (alpha * beta).sqrt() {
+                        continue;
+                    }
+                    rotated = true;
+
+                    let zeta = (beta - alpha) / (two * gamma);
+                    let t =
+                        T::one().copysign(zeta) / (zeta.abs() + (T::one() + zeta * zeta).sqrt());
+                    let c = (T::one() + t * t).sqrt().inv();
+                    let s = c * t;
+
+                    rotate(&mut cols, p, q, c, s);
+                    rotate(&mut v, p, q, c, s);
+                }
+            }
+            if !rotated {
+                converged = true;
+                break;
+            }
+        }
+        if !converged {
+            return Err(LinAlgError::NoConvergence {
+                iterations: MAX_SWEEPS,
+            });
+        }
+
+        let mut order: Vec<usize> = (0..n).collect();
+        let norms: Vec<T> = cols.iter().map(|c| c.norm_l2()).collect();
+        order.sort_by(|&a, &b| norms[b].total_cmp(&norms[a]));
+
+        let sigma: vec::Vec<T> = order.iter().map(|&j| norms[j]).collect();
+        let mut us: Vec<vec::Vec<T>> = Vec::with_capacity(n);
+        for &j in &order {
+            let u = if norms[j] > T::zero() {
+                &cols[j] / norms[j]
+            } else {
+                complete_basis(&us, m)
+            };
+            us.push(u);
+        }
+
+        Ok(Svd {
+            u: Mat2d::from_cols(us),
+            sigma,
+            vt: Mat2d::from(order.iter().map(|&j| v[j].clone()).collect::<Vec<_>>()),
+        })
+    }
+
+    pub fn numerical_rank(&self, tol: T) -> Result<usize, LinAlgError> {
+        Ok(self.svd()?.numerical_rank(tol))
+    }
+
+    pub fn pseudo_inverse(&self) -> Result<Self, LinAlgError> {
+        Ok(self.svd()?.pseudo_inverse())
+    }
+
+    pub fn condition_number(&self) -> Result<T, LinAlgError> {
+        Ok(self.svd()?.condition_number())
+    }
+
+    pub fn low_rank_approximation(&self, k: usize) -> Result<Self, LinAlgError> {
+        Ok(self.svd()?.truncate(k).compose())
+    }
+}
+
+fn rotate<T: RealField>(vs: &mut [vec::Vec<T>], p: usize, q: usize, c: T, s: T) {
+    let (a, b) = vs.split_at_mut(q);
+    let (x, y) = (&mut a[p], &mut b[0]);
+    for i in 0..x.len() {
+        let (xp, yq) = (x[i], y[i]);
+        x[i] = c * xp - s * yq;
+        y[i] = s * xp + c * yq;
+    }
+}
+
+// A unit vector orthogonal to every vector in basis, used for the left
+// singular vectors of zero singular values
+fn complete_basis<T: RealField>(basis: &[vec::Vec<T>], m: usize) -> vec::Vec<T> {
+    (0..m)
+        .map(|i| {
+            let mut e = vec::Vec::zeros(m);
+            e[i] = T::one();
+            for b in basis {
+                let d = b.dot(&e);
+                e.sub_scaled(d, b);
+            }
+            e
+        })
+        .map(|e| {
+            let norm = e.norm_l2();
+            (norm, e)
+        })
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(norm, e)| &e / norm)
+        .unwrap_or_else(|| vec::Vec::zeros(m))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::{Mat2d, Tolerance};
+
+    fn close(a: &Mat2d<f64>, b: &Mat2d<f64>) -> bool {
+        a.approx_eq(b, &Tolerance::new(0.0, 1e-12))
+    }
+
+    #[test]
+    fn factors_reconstruct_the_matrix() {
+        for a in [
+            Mat2d::from([[3.0, 2.0, 2.0], [2.0, 3.0, -2.0]]),
+            Mat2d::from([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]),
+            Mat2d::from([[2.0, 0.0], [0.0, 0.0]]),
+        ] {
+            let svd = a.svd().unwrap();
+            assert!(close(&svd.compose(), &a));
+            assert!(svd.sigma.as_slice().windows(2).all(|w| w[0] >= w[1]));
+
+            let k = svd.sigma.len();
+            let identity = Mat2d::identity(k, k).unwrap();
+            assert!(close(&(&svd.u.transpose() * &svd.u).unwrap(), &identity));
+            assert!(close(&(&svd.vt * &svd.vt.transpose()).unwrap(), &identity));
+        }
+    }
+
+    #[test]
+    fn singular_values_and_rank() {
+        let a = Mat2d::from([[3.0, 2.0, 2.0], [2.0, 3.0, -2.0]]);
+        let svd = a.svd().unwrap();
+        assert!((svd.sigma[0] - 5.0_f64).abs() < 1e-12);
+        assert!((svd.sigma[1] - 3.0_f64).abs() < 1e-12);
+
+        let rank_one = Mat2d::from([[1.0, 2.0], [2.0, 4.0]]);
+        let svd = rank_one.svd().unwrap();
+        assert_eq!(svd.numerical_rank(svd.default_tolerance()), 1);
+        assert_eq!(svd.condition_number(), f64::INFINITY);
+    }
+
+    #[test]
+    fn pseudo_inverse_of_a_full_rank_matrix_is_its_inverse() {
+        let a = Mat2d::from([[4.0, 7.0], [2.0, 6.0]]);
+        let pinv = a.pseudo_inverse().unwrap();
+        assert!(close(&pinv, &a.inverse().unwrap()));
+    }
+
+    #[test]
+    fn non_finite_entries_do_not_panic() {
+        let a = Mat2d::from([[f64::NAN, 1.0], [1.0, 2.0]]);
+        let _ = a.svd();
+        let b = Mat2d::from([[f64::INFINITY, 1.0], [1.0, 2.0]]);
+        let _ = b.svd();
+    }
+}
@@ -0,0 +1,280 @@
+use num_traits::identities::Zero;
+
+use super::{vec, Mat2d, OrderedField, RealField};
+
+// When a computed value counts as zero: x is negligible against a scale s
+// when |x| <= absolute + relative * s. For an m x n matrix the scale of an
+// entry is max(m, n) times the largest magnitude in its column, so a large
+// column does not swamp a small one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance<T> {
+    pub absolute: T,
+    pub relative: T,
+}
+
+impl<T: OrderedField> Tolerance<T> {
+    pub fn new(absolute: T, relative: T) -> Self {
+        Self { absolute, relative }
+    }
+
+    // Only exact zeros are negligible, which is right for Rational and other
+    // exact fields
+    pub fn exact() -> Self {
+        Self::new(T::zero(), T::zero())
+    }
+
+    pub fn threshold(&self, scale: T) -> T {
+        self.absolute.clone() + self.relative.clone() * scale
+    }
+
+    pub fn is_negligible(&self, x: &T, scale: T) -> bool {
+        x.abs() <= self.threshold(scale)
+    }
+
+    // a and b agree relative to the larger of the two
+    pub fn approx_eq(&self, a: &T, b: &T) -> bool {
+        let (x, y) = (a.abs(), b.abs());
+        self.is_negligible(&(a.clone() - b.clone()), max(x, y))
+    }
+}
+
+// ε relative and no absolute part, giving the max(m, n)·ε·scale cutoff that
+// Svd::default_tolerance uses, so that both ranks agree
+impl<T: RealField> Default for Tolerance<T> {
+    fn default() -> Self {
+        Self::new(T::zero(), T::epsilon())
+    }
+}
+
+impl<T: OrderedField> Mat2d<T> {
+    // max_i Σ_j |a_ij|, the largest row sum
+    pub fn norm_inf(&self) -> T {
+        self.iter().map(|v| v.norm_l1()).fold(T::zero(), max)
+    }
+
+    // Replaces every entry negligible against its column with an exact zero
+    pub fn snap(&mut self, tol: &Tolerance<T>) {
+        let zero = Scaled::new(self, tol);
+        for v in self.iter_mut() {
+            for (col, x) in v.iter_mut().enumerate() {
+                if zero.is_zero(x, col) {
+                    *x = T::zero();
+                }
+            }
+        }
+    }
+
+    pub fn snapped(&self, tol: &Tolerance<T>) -> Self {
+        let mut mat = self.clone();
+        mat.snap(tol);
+        mat
+    }
+
+    // Same shape and every entry within tolerance, against max(m, n) times
+    // the larger norm
+    pub fn approx_eq(&self, other: &Self, tol: &Tolerance<T>) -> bool {
+        if self.shape() != other.shape() {
+            return false;
+        }
+        let (m, n) = self.shape();
+        let scale = count::<T>(m.max(n)) * max(self.norm_inf(), other.norm_inf());
+        let threshold = tol.threshold(scale);
+        self.iter()
+            .zip(other.iter())
+            .all(|(u, v)| within(u, v, &threshold))
+    }
+}
+
+impl<T: OrderedField> vec::Vec<T> {
+    // Replaces every entry negligible against len times the largest one with
+    // an exact zero
+    pub fn snap(&mut self, tol: &Tolerance<T>) {
+        let threshold = tol.threshold(count::<T>(self.len()) * self.norm_inf());
+        for x in self.iter_mut() {
+            if x.abs() <= threshold {
+                *x = T::zero();
+            }
+        }
+    }
+
+    // Same length and every entry within tolerance, against len times the
+    // larger norm
+    pub fn approx_eq(&self, other: &Self, tol: &Tolerance<T>) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+        let scale = count::<T>(self.len()) * max(self.norm_inf(), other.norm_inf());
+        within(self, other, &tol.threshold(scale))
+    }
+}
+
+fn within<T: OrderedField>(u: &vec::Vec<T>, v: &vec::Vec<T>, threshold: &T) -> bool {
+    u.iter()
+        .zip(v.iter())
+        .all(|(x, y)| (x.clone() - y.clone()).abs() <= *threshold)
+}
+
+fn max<T: OrderedField>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+// n as an element of T, without needing a conversion from integers
+pub(super) fn count<T: OrderedField>(n: usize) -> T {
+    (0..n).fold(T::zero(), |acc, _| acc + T::one())
+}
+
+// How elimination decides what is zero. The test is only ever applied to
+// pivot candidates and to the entries row operations produce, never to
+// rewrite the caller's input.
+pub(super) trait ZeroTest<T> {
+    // Whether x, an entry of column col, counts as zero
+    fn is_zero(&self, x: &T, col: usize) -> bool;
+
+    // Notes the entries of a row about to be updated, whose magnitudes bound
+    // the rounding error of the result
+    fn record(&mut self, _row: &[T]) {}
+
+    // Follows a column swap from complete pivoting
+    fn swap_cols(&mut self, _a: usize, _b: usize) {}
+}
+
+// Only exact zeros are zero
+pub(super) struct Exact;
+
+impl<T: Zero> ZeroTest<T> for Exact {
+    fn is_zero(&self, x: &T, _: usize) -> bool {
+        x.is_zero()
+    }
+}
+
+// Zero within tolerance of max(m, n) times the largest magnitude that has
+// gone into the entry's column
+pub(super) struct Scaled<T> {
+    tol: Tolerance<T>,
+    dim: T,
+    scales: std::vec::Vec<T>,
+}
+
+impl<T: OrderedField> Scaled<T> {
+    pub(super) fn new(mat: &Mat2d<T>, tol: &Tolerance<T>) -> Self {
+        let (m, n) = mat.shape();
+        let mut scales = vec![T::zero(); n];
+        for v in mat.iter() {
+            for (scale, x) in scales.iter_mut().zip(v.iter()) {
+                *scale = max(scale.clone(), x.abs());
+            }
+        }
+        Self {
+            tol: tol.clone(),
+            dim: count(m.max(n)),
+            scales,
+        }
+    }
+}
+
+impl<T: OrderedField> ZeroTest<T> for Scaled<T> {
+    fn is_zero(&self, x: &T, col: usize) -> bool {
+        x.is_zero()
+            || self
+                .tol
+                .is_negligible(x, self.dim.clone() * self.scales[col].clone())
+    }
+
+    fn record(&mut self, row: &[T]) {
+        for (scale, x) in self.scales.iter_mut().zip(row) {
+            *scale = max(scale.clone(), x.abs());
+        }
+    }
+
+    fn swap_cols(&mut self, a: usize, b: usize) {
+        self.scales.swap(a, b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::{vec, Mat2d, PivotStrategy, Pivoting, SolutionSet, Tolerance};
+
+    fn pivoting(strategy: PivotStrategy) -> Pivoting<f64> {
+        Pivoting::new(strategy, Tolerance::default())
+    }
+
+    #[test]
+    fn rref_snaps_residue_to_positive_zero() {
+        let a = Mat2d::from([[2.0, 1.0, 1.0], [4.0, -6.0, 0.0], [-2.0, 7.0, 2.0]]);
+        let rref = a.rref_with(pivoting(PivotStrategy::FirstNonZero)).rref;
+        assert_eq!(rref, Mat2d::identity(3, 3).unwrap());
+        for v in rref.iter() {
+            assert!(v.iter().all(|x| !(*x == 0.0 && x.is_sign_negative())));
+        }
+    }
+
+    #[test]
+    fn small_input_entries_are_kept() {
+        let a = Mat2d::from([[1e6, 2e6], [0.5, 0.02]]);
+        let b = vec::Vec::from([3e6, 0.52]);
+        let solution = a.solve_with(&b, pivoting(PivotStrategy::Partial)).unwrap();
+        let SolutionSet::Unique(x) = solution else {
+            panic!("expected a unique solution, got {solution:?}");
+        };
+        assert!(x.approx_eq(&vec::Vec::from([1.0, 1.0]), &Tolerance::new(0.0, 1e-12)));
+
+        let d = Mat2d::from([[1e9, 0.0], [0.0, 1.0]]);
+        assert_eq!(d.rank_with(pivoting(PivotStrategy::Partial)), 2);
+        assert!(d.inverse_with(pivoting(PivotStrategy::Partial)).is_ok());
+    }
+
+    #[test]
+    fn rank_agrees_with_svd() {
+        let close = Mat2d::from([[1.0, 1.0], [1.0, 1.0 + 1e-9]]);
+        let svd = close.svd().unwrap();
+        assert_eq!(
+            close.rank_with(pivoting(PivotStrategy::Partial)),
+            svd.numerical_rank(svd.default_tolerance())
+        );
+        assert_eq!(close.rank_with(pivoting(PivotStrategy::Partial)), 2);
+
+        let singular = Mat2d::from([[0.1, 0.2, 0.3], [0.4, 0.5, 0.6], [0.7, 0.8, 0.9]]);
+        for strategy in [
+            PivotStrategy::FirstNonZero,
+            PivotStrategy::Partial,
+            PivotStrategy::Complete,
+        ] {
+            assert_eq!(singular.rank_with(pivoting(strategy)), 2);
+            assert!(singular.inverse_with(pivoting(strategy)).is_err());
+        }
+    }
+
+    #[test]
+    fn solve_ignores_residue_in_b() {
+        let a = Mat2d::from([[0.1, 0.2, 0.3], [0.4, 0.5, 0.6], [0.7, 0.8, 0.9]]);
+        let consistent = vec::Vec::from([0.6, 1.5, 2.4]);
+        let solution = a
+            .solve_with(&consistent, pivoting(PivotStrategy::Partial))
+            .unwrap();
+        assert!(matches!(solution, SolutionSet::Infinite { .. }));
+
+        let inconsistent = vec::Vec::from([1.0, 0.0, 0.0]);
+        let solution = a
+            .solve_with(&inconsistent, pivoting(PivotStrategy::Partial))
+            .unwrap();
+        assert!(!solution.is_consistent());
+    }
+
+    #[test]
+    fn exact_tolerance_only_snaps_zeros() {
+        let mut v = vec::Vec::from([1e-300, -0.0, 1.0_f64]);
+        v.snap(&Tolerance::exact());
+        assert_eq!(v[0], 1e-300);
+        assert!(v[1].is_sign_positive());
+
+        let u = vec::Vec::from([1.0, 2.0]);
+        let w = vec::Vec::from([1.0 + f64::EPSILON, 2.0]);
+        assert!(u.approx_eq(&w, &Tolerance::default()));
+        assert!(!u.approx_eq(&w, &Tolerance::exact()));
+    }
+}
@@ -1,11 +1,13 @@
 use std::{
     fmt::Debug,
-    ops::{Div, Index, IndexMut, Mul, Sub},
+    ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
     slice,
 };
 
 use num_traits::identities;
 
+use super::{Field, Mat2d, OrderedField, RealField, Ring};
+
 #[derive(Clone, PartialEq)]
 pub struct Vec<T> {
     vec: std::vec::Vec<T>,
@@ -14,7 +16,7 @@ pub struct Vec<T> {
 impl<T> Vec<T> {
     pub fn zeros(n: usize) -> Self
     where
-        T: identities::Zero + Copy,
+        T: identities::Zero + Clone,
     {
         Self {
             vec: vec![T::zero(); n],
@@ -23,7 +25,7 @@ impl<T> Vec<T> {
 
     pub fn ones(n: usize) -> Self
     where
-        T: identities::One + Copy,
+        T: identities::One + Clone,
     {
         Self {
             vec: vec![T::one(); n],
@@ -42,6 +44,10 @@ impl<T> Vec<T> {
         self.vec.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
     pub fn get(&self, n: usize) -> Option<&T> {
         self.vec.get(n)
     }
@@ -52,85 +58,370 @@ impl<T> Vec<T> {
     {
         self.vec.resize(n, value)
     }
-}
 
-impl<T> Sub<&Vec<T>> for &Vec<T>
-where
-    T: Sub<Output = T> + Copy,
-{
-    type Output = Vec<T>;
-    fn sub(self, rhs: &Vec<T>) -> Self::Output {
+    pub fn insert(&mut self, index: usize, value: T) {
+        self.vec.insert(index, value)
+    }
+
+    pub fn remove(&mut self, index: usize) -> T {
+        self.vec.remove(index)
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.vec
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.vec
+    }
+
+    pub fn hadamard(&self, rhs: &Self) -> Self
+    where
+        T: Mul<Output = T> + Clone,
+    {
         assert!(
             self.vec.len() == rhs.vec.len(),
+            "two vectors in a hadamard product must have the same length"
+        );
+
+        self.iter()
+            .zip(rhs.iter())
+            .map(|(a, b)| a.clone() * b.clone())
+            .collect()
+    }
+
+    pub fn hadamard_div(&self, rhs: &Self) -> Self
+    where
+        T: Div<Output = T> + Clone,
+    {
+        assert!(
+            self.vec.len() == rhs.vec.len(),
+            "two vectors in a hadamard division must have the same length"
+        );
+
+        self.iter()
+            .zip(rhs.iter())
+            .map(|(a, b)| a.clone() / b.clone())
+            .collect()
+    }
+
+    // self += alpha * x
+    pub fn axpy(&mut self, alpha: T, x: &Self)
+    where
+        T: Add<Output = T> + Mul<Output = T> + Clone,
+    {
+        assert!(
+            self.vec.len() == x.vec.len(),
+            "two vectors in axpy must have the same length"
+        );
+
+        for (a, b) in self.vec.iter_mut().zip(x.iter()) {
+            *a = a.clone() + alpha.clone() * b.clone();
+        }
+    }
+
+    // self -= alpha * x
+    pub fn sub_scaled(&mut self, alpha: T, x: &Self)
+    where
+        T: Sub<Output = T> + Mul<Output = T> + Clone,
+    {
+        assert!(
+            self.vec.len() == x.vec.len(),
             "two vectors in subtraction must have the same length"
         );
 
-        Vec {
-            vec: self
-                .vec
-                .iter()
-                .zip(rhs.iter())
-                .map(|(a, b)| *a - *b)
-                .collect(),
+        for (a, b) in self.vec.iter_mut().zip(x.iter()) {
+            *a = a.clone() - alpha.clone() * b.clone();
         }
     }
 }
 
-// impl<T, const N: usize> Mul<&Vec<T, N>> for &Vec<T, N>
-// where
-//     T: Mul<Output = T> + identities::Zero + Copy,
-// {
-//     type Output = Vec<T, N>;
-
-//     fn mul(self, rhs: &Vec<T, N>) -> Self::Output {
-//         let mut vec = self.vec.clone();
-//         for (vec, rhs) in self.vec.iter().zip(vec.iter_mut()) {
-//             *vec = *vec * *rhs;
-//         }
-//         Vec { vec }
-//     }
-// }
-
-// impl<T, F, const N: usize> Mul<F> for &mut Vec<T, N>
-// where
-//     F: Copy,
-//     T: Mul<F, Output = T> + identities::Zero + Copy,
-// {
-//     type Output = Self;
-
-//     fn mul(self, rhs: F) -> Self::Output {
-//         for v in &mut self.vec {
-//             *v = *v * rhs;
-//         }
-//         self
-//     }
-// }
-
-impl<T, F> Mul<F> for &Vec<T>
+impl<T> Vec<T> {
+    pub fn dot(&self, other: &Self) -> T
+    where
+        T: identities::Zero + Mul<Output = T> + Add<Output = T> + Clone,
+    {
+        assert!(
+            self.vec.len() == other.vec.len(),
+            "two vectors in a dot product must have the same length"
+        );
+        self.iter()
+            .zip(other.iter())
+            .fold(T::zero(), |acc, (a, b)| acc + a.clone() * b.clone())
+    }
+
+    pub fn cross(&self, other: &Self) -> Self
+    where
+        T: Ring,
+    {
+        assert!(
+            self.vec.len() == 3 && other.vec.len() == 3,
+            "the cross product is only defined for vectors of length 3"
+        );
+        let (a, b) = (&self.vec, &other.vec);
+        let term = |i: usize, j: usize| a[i].clone() * b[j].clone() - a[j].clone() * b[i].clone();
+        Self {
+            vec: vec![term(1, 2), term(2, 0), term(0, 1)],
+        }
+    }
+
+    // self * otherᵀ
+    pub fn outer(&self, other: &Self) -> Mat2d<T>
+    where
+        T: Ring,
+    {
+        Mat2d::from(
+            self.iter()
+                .map(|a| other.iter().map(|b| a.clone() * b.clone()).collect())
+                .collect::<std::vec::Vec<Self>>(),
+        )
+    }
+
+    // Σ |x_i|
+    pub fn norm_l1(&self) -> T
+    where
+        T: OrderedField,
+    {
+        self.iter().fold(T::zero(), |acc, x| acc + x.abs())
+    }
+
+    // Euclidean length
+    pub fn norm_l2(&self) -> T
+    where
+        T: RealField,
+    {
+        self.dot(self).sqrt()
+    }
+
+    // max |x_i|
+    pub fn norm_inf(&self) -> T
+    where
+        T: OrderedField,
+    {
+        self.iter()
+            .map(|x| x.abs())
+            .fold(T::zero(), |acc, x| if x > acc { x } else { acc })
+    }
+
+    // (Σ |x_i|^p)^(1/p)
+    pub fn norm_p(&self, p: T) -> T
+    where
+        T: RealField,
+    {
+        self.iter()
+            .fold(T::zero(), |acc, x| acc + x.abs().powf(p))
+            .powf(p.inv())
+    }
+
+    // Unit vector in the same direction, or None for the zero vector
+    pub fn normalize(&self) -> Option<Self>
+    where
+        T: RealField,
+    {
+        let norm = self.norm_l2();
+        if norm == T::zero() {
+            None
+        } else {
+            Some(self / norm)
+        }
+    }
+
+    // Angle in radians, or None if either vector is zero
+    pub fn angle_between(&self, other: &Self) -> Option<T>
+    where
+        T: RealField,
+    {
+        let norms = self.norm_l2() * other.norm_l2();
+        if norms == T::zero() {
+            return None;
+        }
+        // rounding can push the cosine just outside [-1, 1]
+        let cos = self.dot(other) / norms;
+        let cos = if cos > T::one() {
+            T::one()
+        } else if cos < -T::one() {
+            -T::one()
+        } else {
+            cos
+        };
+        Some(cos.acos())
+    }
+
+    // (self·onto / onto·onto) onto, or None if onto is zero
+    pub fn project_onto(&self, onto: &Self) -> Option<Self>
+    where
+        T: Field,
+    {
+        let denom = onto.dot(onto);
+        if denom.is_zero() {
+            return None;
+        }
+        Some(onto * (self.dot(onto) / denom))
+    }
+
+    // The component of self orthogonal to from, so that
+    // self = project_onto(from) + reject_from(from)
+    pub fn reject_from(&self, from: &Self) -> Option<Self>
+    where
+        T: Field,
+    {
+        self.project_onto(from).map(|p| self - p)
+    }
+
+    pub fn distance(&self, other: &Self) -> T
+    where
+        T: RealField,
+    {
+        (self - other).norm_l2()
+    }
+}
+
+macro_rules! impl_elementwise_op {
+    ($op:ident, $method:ident, $op_assign:ident, $method_assign:ident, $name:literal) => {
+        impl<T> $op_assign<&Vec<T>> for Vec<T>
+        where
+            T: $op<Output = T> + Clone,
+        {
+            fn $method_assign(&mut self, rhs: &Vec<T>) {
+                assert!(
+                    self.vec.len() == rhs.vec.len(),
+                    concat!("two vectors in ", $name, " must have the same length")
+                );
+
+                for (a, b) in self.vec.iter_mut().zip(rhs.iter()) {
+                    *a = a.clone().$method(b.clone());
+                }
+            }
+        }
+
+        impl<T> $op_assign<Vec<T>> for Vec<T>
+        where
+            T: $op<Output = T> + Clone,
+        {
+            fn $method_assign(&mut self, rhs: Vec<T>) {
+                self.$method_assign(&rhs)
+            }
+        }
+
+        impl<T> $op<&Vec<T>> for Vec<T>
+        where
+            T: $op<Output = T> + Clone,
+        {
+            type Output = Vec<T>;
+
+            fn $method(mut self, rhs: &Vec<T>) -> Self::Output {
+                self.$method_assign(rhs);
+                self
+            }
+        }
+
+        impl<T> $op<Vec<T>> for Vec<T>
+        where
+            T: $op<Output = T> + Clone,
+        {
+            type Output = Vec<T>;
+
+            fn $method(self, rhs: Vec<T>) -> Self::Output {
+                self.$method(&rhs)
+            }
+        }
+
+        impl<T> $op<&Vec<T>> for &Vec<T>
+        where
+            T: $op<Output = T> + Clone,
+        {
+            type Output = Vec<T>;
+
+            fn $method(self, rhs: &Vec<T>) -> Self::Output {
+                self.clone().$method(rhs)
+            }
+        }
+
+        impl<T> $op<Vec<T>> for &Vec<T>
+        where
+            T: $op<Output = T> + Clone,
+        {
+            type Output = Vec<T>;
+
+            fn $method(self, rhs: Vec<T>) -> Self::Output {
+                self.clone().$method(&rhs)
+            }
+        }
+    };
+}
+
+impl_elementwise_op!(Add, add, AddAssign, add_assign, "addition");
+impl_elementwise_op!(Sub, sub, SubAssign, sub_assign, "subtraction");
+
+macro_rules! impl_scalar_op {
+    ($op:ident, $method:ident, $op_assign:ident, $method_assign:ident) => {
+        impl<T> $op_assign<T> for Vec<T>
+        where
+            T: $op<Output = T> + Clone,
+        {
+            fn $method_assign(&mut self, rhs: T) {
+                for x in &mut self.vec {
+                    *x = x.clone().$method(rhs.clone());
+                }
+            }
+        }
+
+        impl<T> $op<T> for Vec<T>
+        where
+            T: $op<Output = T> + Clone,
+        {
+            type Output = Vec<T>;
+
+            fn $method(mut self, rhs: T) -> Self::Output {
+                self.$method_assign(rhs);
+                self
+            }
+        }
+
+        impl<T> $op<T> for &Vec<T>
+        where
+            T: $op<Output = T> + Clone,
+        {
+            type Output = Vec<T>;
+
+            fn $method(self, rhs: T) -> Self::Output {
+                Vec {
+                    vec: self
+                        .vec
+                        .iter()
+                        .map(|x| x.clone().$method(rhs.clone()))
+                        .collect(),
+                }
+            }
+        }
+    };
+}
+
+impl_scalar_op!(Mul, mul, MulAssign, mul_assign);
+impl_scalar_op!(Div, div, DivAssign, div_assign);
+
+impl<T> Neg for Vec<T>
 where
-    F: Copy,
-    T: Mul<F, Output = T> + identities::Zero + Copy,
+    T: Neg<Output = T> + Clone,
 {
     type Output = Vec<T>;
 
-    fn mul(self, rhs: F) -> Self::Output {
-        Vec {
-            vec: self.vec.iter().map(|x| *x * rhs).collect(),
+    fn neg(mut self) -> Self::Output {
+        for x in &mut self.vec {
+            *x = -x.clone();
         }
+        self
     }
 }
 
-impl<T, F> Div<F> for &Vec<T>
+impl<T> Neg for &Vec<T>
 where
-    F: Copy,
-    T: Div<F, Output = T> + identities::Zero + Copy,
+    T: Neg<Output = T> + Clone,
 {
     type Output = Vec<T>;
 
-    fn div(self, rhs: F) -> Self::Output {
-        Vec {
-            vec: self.vec.iter().map(|x| *x / rhs).collect(),
-        }
+    fn neg(self) -> Self::Output {
+        -self.clone()
     }
 }
 
@@ -171,3 +462,91 @@ impl<T> FromIterator<T> for Vec<T> {
         Vec { vec }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Vec;
+
+    #[test]
+    fn elementwise_and_scalar_operators() {
+        let a = Vec::from([1, 2, 3]);
+        let b = Vec::from([4, 5, 6]);
+        assert_eq!(&a + &b, Vec::from([5, 7, 9]));
+        assert_eq!(b.clone() - a.clone(), Vec::from([3, 3, 3]));
+        assert_eq!(-&a, Vec::from([-1, -2, -3]));
+        assert_eq!(&a * 2, Vec::from([2, 4, 6]));
+        assert_eq!(b.clone() / 2, Vec::from([2, 2, 3]));
+        assert_eq!(a.hadamard(&b), Vec::from([4, 10, 18]));
+        assert_eq!(b.hadamard_div(&a), Vec::from([4, 2, 2]));
+
+        let mut c = a.clone();
+        c += &b;
+        c *= 3;
+        assert_eq!(c, Vec::from([15, 21, 27]));
+    }
+
+    #[test]
+    fn in_place_row_updates() {
+        let mut a = Vec::from([1, 2, 3]);
+        a.axpy(2, &Vec::from([1, 0, -1]));
+        assert_eq!(a, Vec::from([3, 2, 1]));
+        a.sub_scaled(3, &Vec::from([1, 1, 0]));
+        assert_eq!(a, Vec::from([0, -1, 1]));
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn lengths_must_match() {
+        let _ = Vec::from([1, 2]) + Vec::from([1, 2, 3]);
+    }
+
+    #[test]
+    fn products_and_norms() {
+        let a = Vec::from([1, 2, 3]);
+        let b = Vec::from([4, 5, 6]);
+        assert_eq!(a.dot(&b), 32);
+        let cross = a.cross(&b);
+        assert_eq!(cross, Vec::from([-3, 6, -3]));
+        assert_eq!((cross.dot(&a), cross.dot(&b)), (0, 0));
+        assert_eq!(
+            Vec::from([1, 2]).outer(&Vec::from([3, 4, 5])),
+            crate::math::Mat2d::from([[3, 4, 5], [6, 8, 10]])
+        );
+
+        let v = Vec::from([3.0, -4.0]);
+        assert_eq!(v.norm_l1(), 7.0);
+        assert_eq!(v.norm_l2(), 5.0);
+        assert_eq!(v.norm_inf(), 4.0);
+        assert!((v.norm_p(2.0) - 5.0_f64).abs() < 1e-12);
+        assert_eq!(v.normalize(), Some(Vec::from([0.6, -0.8])));
+        assert_eq!(Vec::<f64>::zeros(2).normalize(), None);
+        assert_eq!(v.distance(&Vec::from([0.0, 0.0])), 5.0);
+    }
+
+    #[test]
+    fn angles_and_projections() {
+        let x = Vec::from([1.0, 0.0]);
+        let y = Vec::from([1.0, 1.0]);
+        let angle = x.angle_between(&y).unwrap();
+        assert!((angle - std::f64::consts::FRAC_PI_4).abs() < 1e-12);
+        // the cosine of parallel vectors is clamped, so it cannot round past
+        // 1 into a NaN angle
+        let v = Vec::from([0.1, 0.2, 0.3]);
+        let angle = v.angle_between(&(&v * 3.0)).unwrap();
+        assert!((0.0..1e-7).contains(&angle));
+        assert_eq!(x.angle_between(&Vec::zeros(2)), None);
+
+        let p = y.project_onto(&x).unwrap();
+        let r = y.reject_from(&x).unwrap();
+        assert_eq!(p, Vec::from([1.0, 0.0]));
+        assert_eq!(r, Vec::from([0.0, 1.0]));
+        assert_eq!(&p + &r, y);
+        assert_eq!(y.project_onto(&Vec::zeros(2)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "length 3")]
+    fn cross_needs_three_dimensions() {
+        let _ = Vec::from([1, 2]).cross(&Vec::from([3, 4]));
+    }
+}
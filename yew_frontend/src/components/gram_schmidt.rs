@@ -0,0 +1,69 @@
+use std::fmt::Display;
+
+use yew::{function_component, html, Html, Properties};
+
+use crate::components::Mat;
+use linear_alg::math::{gram_schmidt, row_op::subscript, GramSchmidtStep, Mat2d, RealField};
+
+#[derive(Properties, PartialEq)]
+pub struct Props<T>
+where
+    T: PartialEq,
+{
+    pub mat: Mat2d<T>,
+}
+
+fn describe<T: Display>(step: &GramSchmidtStep<T>) -> String {
+    match step {
+        GramSchmidtStep::Project {
+            index, onto, coeff, ..
+        } => format!(
+            "Subtract {coeff} × q{} from column {}",
+            subscript(*onto),
+            index + 1
+        ),
+        GramSchmidtStep::Normalize { index, norm, .. } => {
+            format!("Divide column {} by its norm {norm}", index + 1)
+        }
+        GramSchmidtStep::Dependent { index, .. } => format!(
+            "Column {} is a combination of the previous columns, skip it",
+            index + 1
+        ),
+    }
+}
+
+// Orthonormalizes the columns of the matrix
+#[function_component(GramSchmidtSteps)]
+pub fn gram_schmidt_steps<T>(props: &Props<T>) -> Html
+where
+    T: RealField + Display + 'static,
+{
+    let (_, n) = props.mat.shape();
+    let cols: Vec<_> = (0..n).map(|j| props.mat.col(j)).collect();
+    let trace = gram_schmidt(&cols);
+
+    html! {
+        <div class="dflex dflex-col dflex-gap-lg">
+            <h1>{"Gram–Schmidt"}</h1>
+            {for trace.steps.into_iter().map(|step| {
+                let vector = match &step {
+                    GramSchmidtStep::Project { vector, .. } | GramSchmidtStep::Normalize { vector, .. } => {
+                        Some(Mat2d::from_cols(vec![vector.clone()]))
+                    }
+                    GramSchmidtStep::Dependent { .. } => None,
+                };
+                html! {
+                    <div class="dflex dflex-justify-center dflex-gap-md">
+                        {for vector.map(|mat| html! { <Mat::<T> {mat}/> })}
+                        <span>{describe(&step)}</span>
+                    </div>
+                }
+            })}
+            if !trace.basis.is_empty() {
+                <div class="dflex dflex-row dflex-justify-center dflex-gap-md">
+                    <Mat::<T> mat={Mat2d::from_cols(trace.basis)}/>
+                </div>
+            }
+        </div>
+    }
+}
@@ -1,6 +1,5 @@
 use std::fmt::Display;
 
-use num_traits::{identities, Float, FromPrimitive, ToPrimitive};
 use wasm_bindgen::JsCast;
 use web_sys::{Element, HtmlElement};
 use yew::{function_component, html, use_effect_with_deps, use_node_ref, Html, Properties};
@@ -10,14 +9,14 @@ use linear_alg::math::Mat2d;
 #[derive(Properties, PartialEq)]
 pub struct Props<T>
 where
-    T: PartialEq + identities::Zero + Copy,
+    T: PartialEq,
 {
     pub mat: Mat2d<T>,
 }
 #[function_component(Mat)]
 pub fn mat<T>(props: &Props<T>) -> Html
 where
-    T: Float + Display + 'static + FromPrimitive + ToPrimitive,
+    T: PartialEq + Clone + Display + 'static,
 {
     let matrix_ref = use_node_ref();
     let lparen_ref = use_node_ref();
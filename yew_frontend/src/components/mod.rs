@@ -1,5 +1,6 @@
 mod dimension_input;
 mod display;
+mod gram_schmidt;
 mod input;
 mod mat;
 mod mat_edit;
@@ -7,6 +8,7 @@ mod nav;
 mod steps;
 
 pub use dimension_input::DimensionInput;
+pub use gram_schmidt::GramSchmidtSteps;
 pub use mat::Mat;
 pub use mat_edit::MatEdit;
 pub use nav::Nav;
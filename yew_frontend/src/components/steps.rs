@@ -1,10 +1,9 @@
-use std::fmt::{Debug, Display};
+use std::fmt::Display;
 
-use num_traits::{Float, FromPrimitive};
 use yew::{function_component, html, virtual_dom::VNode, Html, Properties};
 
 use crate::components::Mat;
-use linear_alg::math::{mat::Step, Mat2d};
+use linear_alg::math::{Mat2d, OrderedField, Phase, PivotStrategy, Pivoting, Tolerance};
 
 #[derive(Properties, PartialEq)]
 pub struct Props<T>
@@ -12,15 +11,28 @@ where
     T: PartialEq,
 {
     pub mat: Mat2d<T>,
+    #[prop_or_default]
+    pub strategy: PivotStrategy,
+    // exact comparisons when None
+    #[prop_or_default]
+    pub tolerance: Option<Tolerance<T>>,
 }
 
 #[function_component(Steps)]
 pub fn steps<T>(props: &Props<T>) -> Html
 where
-    T: PartialEq + Copy + Float + Display + FromPrimitive + 'static + Debug,
+    T: OrderedField + Display + 'static,
 {
-    let rref = props.mat.rref();
-    let steps = props.mat.row_reduced_verbose();
+    let tolerance = props.tolerance.clone().unwrap_or_else(Tolerance::exact);
+    let pivoting = Pivoting::new(props.strategy, tolerance);
+    let rref = props.mat.rref_with(pivoting.clone());
+    let reordered = rref
+        .col_permutation
+        .iter()
+        .enumerate()
+        .any(|(j, &k)| j != k);
+    let determinant = props.mat.determinant_verbose_with(pivoting.clone()).ok();
+    let inverse = props.mat.inverse_verbose_with(pivoting.clone()).ok();
 
     let equations = gloo_utils::document()
         .create_element_ns(Some("http://www.w3.org/1998/Math/MathML"), "math")
@@ -28,23 +40,65 @@ where
 
     html! {
         <div class="dflex dflex-col dflex-gap-lg" style="margin-top: 2em;">
-            {for steps.into_iter().map(|(mat, step)| html! {
-                <div class="dflex dflex-justify-center dflex-gap-md">
-                    <Mat::<T> {mat}/>
-                    <span>{
-                        match step {
-                            Step::Swap{from, to} => format!("Swap row {from} with row {to}"),
-                            Step::Sub{scale, from, to} => format!("Mutliply row {from} by {scale} and subtract from row {to}"),
-                        }
-                    }</span>
-                </div>
-            } )}
-
+            {for [
+                (Phase::Forward, "Forward elimination"),
+                (Phase::Normalize, "Scale pivots to 1"),
+                (Phase::Backward, "Backward elimination"),
+            ]
+            .into_iter()
+            .map(|(phase, title)| {
+                let steps: Vec<_> = rref.steps.iter().filter(|(_, _, p)| *p == phase).collect();
+                html! {
+                    if !steps.is_empty() {
+                        <h2>{title}</h2>
+                        {for steps.into_iter().map(|(mat, step, _)| html! {
+                            <div class="dflex dflex-justify-center dflex-gap-md">
+                                <Mat::<T> mat={mat.clone()}/>
+                                <span>{step.to_string()}</span>
+                            </div>
+                        })}
+                    }
+                }
+            })}
 
+            if reordered {
+                <span>{format!(
+                    "Complete pivoting reordered the columns to {}",
+                    rref.col_permutation.iter().map(|k| (k + 1).to_string()).collect::<Vec<_>>().join(", ")
+                )}</span>
+            }
             <h1>{"RREF matrix"}</h1>
             <div class="dflex dflex-row dflex-justify-center dflex-gap-md">
-                <Mat::<T> mat={rref}/>
+                <Mat::<T> mat={rref.rref.clone()}/>
             </div>
+            {for determinant.map(|trace| {
+                let diagonal = trace
+                    .diagonal
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" × ");
+                html! {
+                    <>
+                        <h1>{"Determinant"}</h1>
+                        <span>{format!("Each row or column swap flips the sign, so det = {} × ({diagonal}) = {}", trace.sign, trace.determinant)}</span>
+                    </>
+                }
+            })}
+            {for inverse.map(|trace| html! {
+                <>
+                    <h1>{"Inverse"}</h1>
+                    {for trace.steps.into_iter().map(|(mat, step)| html! {
+                        <div class="dflex dflex-justify-center dflex-gap-md">
+                            <Mat::<T> {mat}/>
+                            <span>{step.to_string()}</span>
+                        </div>
+                    })}
+                    <div class="dflex dflex-row dflex-justify-center dflex-gap-md">
+                        <Mat::<T> mat={trace.inverse}/>
+                    </div>
+                </>
+            })}
             {VNode::VRef(equations.into())}
         </div>
     }
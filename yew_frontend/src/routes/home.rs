@@ -1,17 +1,59 @@
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
-use crate::components::{DimensionInput, MatEdit, Nav, Steps};
-use linear_alg::math::Mat2d;
+use crate::components::{DimensionInput, GramSchmidtSteps, MatEdit, Nav, Steps};
+use linear_alg::math::{parse_decimal, Mat2d, PivotStrategy, Rational, Tolerance};
+
+const STRATEGIES: [(PivotStrategy, &str); 5] = [
+    (PivotStrategy::FirstNonZero, "First non-zero"),
+    (PivotStrategy::Simplest, "Simplest (prefer ±1)"),
+    (PivotStrategy::Partial, "Partial"),
+    (PivotStrategy::ScaledPartial, "Scaled partial"),
+    (PivotStrategy::Complete, "Complete"),
+];
+
+// The fraction each entry reads as in decimal, so 0.1 is 1/10 rather than the
+// binary fraction nearest to it. None when an entry is not finite.
+fn to_rational(mat: &Mat2d<f64>) -> Option<Mat2d<Rational>> {
+    let entries = mat.map(|x| parse_decimal(&x.to_string()));
+    entries
+        .iter()
+        .all(|v| v.iter().all(Option::is_some))
+        .then(|| entries.map(|x| x.clone().unwrap()))
+}
 
 #[function_component(Home)]
 pub fn home() -> Html {
     let dims = use_state(|| (3, 3));
     let mat = use_state(|| Mat2d::<f64>::zeros(3, 3));
+    let fractions = use_state(|| true);
+    let strategy = use_state(PivotStrategy::default);
     let dim_cb = {
         let dims = dims.clone();
         Callback::from(move |(m, n)| dims.set((m, n)))
     };
 
+    let fractions_cb = {
+        let fractions = fractions.clone();
+        Callback::from(move |e: Event| {
+            fractions.set(e.target_unchecked_into::<HtmlInputElement>().checked());
+        })
+    };
+
+    let strategy_cb = {
+        let strategy = strategy.clone();
+        Callback::from(move |e: Event| {
+            let index = e
+                .target_unchecked_into::<HtmlSelectElement>()
+                .selected_index();
+            if let Some(&(selected, _)) =
+                usize::try_from(index).ok().and_then(|i| STRATEGIES.get(i))
+            {
+                strategy.set(selected);
+            }
+        })
+    };
+
     let mat_cb = {
         let mat = mat.clone();
         Callback::from(move |new_mat| {
@@ -41,9 +83,30 @@ pub fn home() -> Html {
             <MatEdit::<f64> mat={(*mat).clone()} onchange={mat_cb}/>
             <div class="dflex dflex-row dflex-gap-sm">
                 <DimensionInput {dim_cb} />
+                <label>
+                    <input type="checkbox" checked={*fractions} onchange={fractions_cb}/>
+                    {"Show fractions"}
+                </label>
+                <label>
+                    {"Pivoting "}
+                    <select onchange={strategy_cb}>
+                        {for STRATEGIES.iter().map(|&(s, name)| html! {
+                            <option selected={s == *strategy}>{name}</option>
+                        })}
+                    </select>
+                </label>
             </div>
 
-            <Steps::<f64> mat={(*mat).clone()}/>
+            if *fractions {
+                if let Some(mat) = to_rational(&mat) {
+                    <Steps::<Rational> {mat} strategy={*strategy}/>
+                } else {
+                    <span>{"Fractions need every entry to be a finite number"}</span>
+                }
+            } else {
+                <Steps::<f64> mat={(*mat).clone()} strategy={*strategy} tolerance={Tolerance::default()}/>
+            }
+            <GramSchmidtSteps::<f64> mat={(*mat).clone()}/>
 
         </main>
         </>